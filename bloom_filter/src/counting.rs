@@ -0,0 +1,125 @@
+use crate::*;
+
+///A bloom filter which keeps a small counter per slot instead of a single bit, allowing
+/// elements to be removed again. Uses eight times the memory of a `BloomFilter` with the same
+/// parameters.
+///
+/// Counters saturate at `u8::MAX` and are never decremented after that, as the true count
+/// is no longer known.
+pub struct CountingBloomFilter<T: Hash> {
+	pub(crate) counters: Vector<u8>,
+	pub(crate) hashes: usize,
+	pub(crate) _marker: PhantomData<T>,
+}
+
+impl<T: Hash> fmt::Debug for CountingBloomFilter<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("CountingBloomFilter")
+			.field("counters", &self.counters.len())
+			.field("hashes", &self.hashes)
+			.finish()
+	}
+}
+
+impl<T: Hash> CountingBloomFilter<T> {
+	///Creates a new filter with `counters` counters and `hashes` hash functions per element.
+	///
+	/// Panics if either `counters` or `hashes` is zero. Has O(n) complexity in `counters`.
+	pub fn new(counters: usize, hashes: usize) -> Self {
+		assert_ne!(counters, 0, "A bloom filter needs at least one counter");
+		assert_ne!(hashes, 0, "A bloom filter needs at least one hash function");
+		let mut vec = Vector::with_capacity(counters);
+		for _ in 0..counters {
+			vec.push(0);
+		}
+		CountingBloomFilter {
+			counters: vec,
+			hashes,
+			_marker: PhantomData,
+		}
+	}
+
+	///Creates a new filter sized to hold `expected_items` elements while keeping the false
+	/// positive rate at or below `false_positive_rate`.
+	///
+	/// Panics if `false_positive_rate` is not within `(0, 1)`. Has O(n) complexity in the derived counter count.
+	pub fn with_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+		let (counters, hashes) = optimal_parameters(expected_items, false_positive_rate);
+		CountingBloomFilter::new(counters, hashes)
+	}
+
+	///Returns the amount of counters in the filter.
+	pub fn counters(&self) -> usize {
+		self.counters.len()
+	}
+
+	///Returns the amount of hash functions used per element.
+	pub fn hashes(&self) -> usize {
+		self.hashes
+	}
+
+	///Checks if every counter is zero.
+	///
+	/// Has O(n) complexity in the counter count.
+	pub fn is_empty(&self) -> bool {
+		self.counters.iter().all(|&c| c == 0)
+	}
+
+	///Adds an element to the filter.
+	///
+	/// Has O(k) complexity where k is the amount of hash functions.
+	pub fn insert(&mut self, elem: &T) {
+		for idx in indices(elem, self.hashes, self.counters.len()) {
+			let counter = &mut self.counters[idx];
+			*counter = counter.saturating_add(1);
+		}
+	}
+
+	///Checks if the element may be in the filter. A `false` is always correct, a `true`
+	/// is wrong at roughly the rate the filter was created for.
+	///
+	/// Has O(k) complexity where k is the amount of hash functions.
+	pub fn contains(&self, elem: &T) -> bool {
+		indices(elem, self.hashes, self.counters.len()).all(|idx| self.counters[idx] != 0)
+	}
+
+	///Removes an element from the filter. Only remove elements that have actually been inserted,
+	/// removing a false positive will introduce false negatives.
+	///
+	/// Returns `false` and leaves the filter untouched if the element is definitely not present.
+	/// Has O(k) complexity where k is the amount of hash functions.
+	pub fn remove(&mut self, elem: &T) -> bool {
+		if !self.contains(elem) {
+			return false;
+		}
+		for idx in indices(elem, self.hashes, self.counters.len()) {
+			let counter = &mut self.counters[idx];
+			if *counter != u8::MAX {
+				*counter -= 1;
+			}
+		}
+		true
+	}
+
+	///Adds every element of `other` to this filter.
+	///
+	/// Panics if the filters don't have the same counter and hash counts. Has O(n) complexity in the counter count.
+	pub fn union(&mut self, other: &CountingBloomFilter<T>) {
+		assert!(
+			self.counters.len() == other.counters.len() && self.hashes == other.hashes,
+			"Cannot union bloom filters with different parameters"
+		);
+		for (counter, other) in self.counters.iter_mut().zip(other.counters.iter()) {
+			*counter = counter.saturating_add(*other);
+		}
+	}
+
+	///Removes every element from the filter without deallocating.
+	///
+	/// Has O(n) complexity in the counter count.
+	pub fn clear(&mut self) {
+		for counter in self.counters.iter_mut() {
+			*counter = 0;
+		}
+	}
+}
@@ -0,0 +1,165 @@
+use std::{
+	collections::hash_map::DefaultHasher,
+	f64::consts::LN_2,
+	fmt,
+	hash::{Hash, Hasher},
+	marker::PhantomData,
+};
+use vector::Vector;
+
+#[cfg(test)]
+pub mod test_bloom;
+
+#[cfg(test)]
+pub mod test_counting;
+
+pub mod counting;
+pub use counting::CountingBloomFilter;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+///A probabilistic set of `T`. May report false positives but never false negatives.
+/// The bits are stored packed into a `Vector<u64>` which is allocated upon creation.
+pub struct BloomFilter<T: Hash> {
+	pub(crate) words: Vector<u64>,
+	pub(crate) bits: usize,
+	pub(crate) hashes: usize,
+	pub(crate) _marker: PhantomData<T>,
+}
+
+impl<T: Hash> fmt::Debug for BloomFilter<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("BloomFilter")
+			.field("bits", &self.bits)
+			.field("hashes", &self.hashes)
+			.field("set_bits", &self.count_ones())
+			.finish()
+	}
+}
+
+impl<T: Hash> BloomFilter<T> {
+	///Creates a new filter with `bits` bits and `hashes` hash functions per element.
+	///
+	/// Panics if either `bits` or `hashes` is zero. Has O(n) complexity in `bits`.
+	pub fn new(bits: usize, hashes: usize) -> Self {
+		assert_ne!(bits, 0, "A bloom filter needs at least one bit");
+		assert_ne!(hashes, 0, "A bloom filter needs at least one hash function");
+		let len = bits.div_ceil(WORD_BITS);
+		let mut words = Vector::with_capacity(len);
+		for _ in 0..len {
+			words.push(0);
+		}
+		BloomFilter {
+			words,
+			bits,
+			hashes,
+			_marker: PhantomData,
+		}
+	}
+
+	///Creates a new filter sized to hold `expected_items` elements while keeping the false
+	/// positive rate at or below `false_positive_rate`.
+	///
+	/// Panics if `false_positive_rate` is not within `(0, 1)`. Has O(n) complexity in the derived bit count.
+	pub fn with_rate(expected_items: usize, false_positive_rate: f64) -> Self {
+		let (bits, hashes) = optimal_parameters(expected_items, false_positive_rate);
+		BloomFilter::new(bits, hashes)
+	}
+
+	///Returns the amount of bits in the filter.
+	pub fn bits(&self) -> usize {
+		self.bits
+	}
+
+	///Returns the amount of hash functions used per element.
+	pub fn hashes(&self) -> usize {
+		self.hashes
+	}
+
+	///Checks if no element has been inserted since creation or the last `clear`.
+	///
+	/// Has O(n) complexity in the bit count.
+	pub fn is_empty(&self) -> bool {
+		self.words.iter().all(|&w| w == 0)
+	}
+
+	///Adds an element to the filter.
+	///
+	/// Has O(k) complexity where k is the amount of hash functions.
+	pub fn insert(&mut self, elem: &T) {
+		for idx in indices(elem, self.hashes, self.bits) {
+			self.words[idx / WORD_BITS] |= 1 << (idx % WORD_BITS);
+		}
+	}
+
+	///Checks if the element may have been inserted. A `false` is always correct, a `true`
+	/// is wrong at roughly the rate the filter was created for.
+	///
+	/// Has O(k) complexity where k is the amount of hash functions.
+	pub fn contains(&self, elem: &T) -> bool {
+		indices(elem, self.hashes, self.bits)
+			.all(|idx| self.words[idx / WORD_BITS] & (1 << (idx % WORD_BITS)) != 0)
+	}
+
+	///Adds every element of `other` to this filter.
+	///
+	/// Panics if the filters don't have the same bit and hash counts. Has O(n) complexity in the bit count.
+	pub fn union(&mut self, other: &BloomFilter<T>) {
+		assert!(
+			self.bits == other.bits && self.hashes == other.hashes,
+			"Cannot union bloom filters with different parameters"
+		);
+		for (word, other) in self.words.iter_mut().zip(other.words.iter()) {
+			*word |= *other;
+		}
+	}
+
+	///Removes every element from the filter without deallocating.
+	///
+	/// Has O(n) complexity in the bit count.
+	pub fn clear(&mut self) {
+		for word in self.words.iter_mut() {
+			*word = 0;
+		}
+	}
+
+	///Returns the amount of bits that are set.
+	///
+	/// Has O(n) complexity in the bit count.
+	pub fn count_ones(&self) -> usize {
+		self.words.iter().map(|w| w.count_ones() as usize).sum()
+	}
+}
+
+///Calculates the bit and hash function counts for `expected_items` elements at `false_positive_rate`.
+pub(crate) fn optimal_parameters(
+	expected_items: usize,
+	false_positive_rate: f64,
+) -> (usize, usize) {
+	assert!(
+		false_positive_rate > 0.0 && false_positive_rate < 1.0,
+		"False positive rate must be between 0 and 1"
+	);
+	let items = expected_items.max(1) as f64;
+	let bits = (-items * false_positive_rate.ln() / (LN_2 * LN_2)).ceil();
+	let hashes = (bits / items * LN_2).round();
+	(bits.max(1.0) as usize, hashes.max(1.0) as usize)
+}
+
+///Returns the `hashes` bit positions for `elem` within a filter of `bits` bits.
+///
+/// Uses double hashing (`h1 + i * h2`) so the element only has to be hashed twice.
+pub(crate) fn indices<T: Hash>(
+	elem: &T,
+	hashes: usize,
+	bits: usize,
+) -> impl Iterator<Item = usize> {
+	let mut hasher = DefaultHasher::new();
+	elem.hash(&mut hasher);
+	let h1 = hasher.finish();
+	//Hash the first hash again rather than the element to get a second independent value.
+	h1.hash(&mut hasher);
+	//An even step could cycle through only part of a power of two sized filter.
+	let h2 = hasher.finish() | 1;
+	(0..hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % bits as u64) as usize)
+}
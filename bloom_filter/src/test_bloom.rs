@@ -0,0 +1,72 @@
+use crate::*;
+
+#[test]
+fn insert_contains() {
+	let mut filter = BloomFilter::new(1024, 4);
+	assert!(filter.is_empty());
+	filter.insert(&1);
+	filter.insert(&2);
+	assert!(filter.contains(&1));
+	assert!(filter.contains(&2));
+	assert!(!filter.is_empty());
+}
+
+#[test]
+fn no_false_negatives() {
+	let mut filter = BloomFilter::with_rate(1000, 0.01);
+	for i in 0..1000 {
+		filter.insert(&i);
+	}
+	for i in 0..1000 {
+		assert!(filter.contains(&i));
+	}
+}
+
+#[test]
+fn false_positive_rate() {
+	let mut filter = BloomFilter::with_rate(1000, 0.01);
+	for i in 0..1000 {
+		filter.insert(&i);
+	}
+	let false_positives = (1000..11000).filter(|i| filter.contains(i)).count();
+	//Expected around 100, leave some room for an unlucky hash distribution.
+	assert!(false_positives < 250, "{} false positives", false_positives);
+}
+
+#[test]
+fn with_rate_parameters() {
+	let filter = BloomFilter::<u32>::with_rate(1000, 0.01);
+	//m = -n ln(p) / ln(2)² ≈ 9586, k = m / n ln(2) ≈ 7
+	assert_eq!(filter.bits(), 9586);
+	assert_eq!(filter.hashes(), 7);
+}
+
+#[test]
+fn union() {
+	let mut a = BloomFilter::new(512, 3);
+	let mut b = BloomFilter::new(512, 3);
+	a.insert(&"a");
+	b.insert(&"b");
+	a.union(&b);
+	assert!(a.contains(&"a"));
+	assert!(a.contains(&"b"));
+	assert!(!b.contains(&"a"));
+}
+
+#[test]
+#[should_panic]
+fn union_mismatched() {
+	let mut a = BloomFilter::<i32>::new(512, 3);
+	let b = BloomFilter::new(512, 4);
+	a.union(&b);
+}
+
+#[test]
+fn clear() {
+	let mut filter = BloomFilter::new(100, 2);
+	filter.insert(&5);
+	assert_ne!(filter.count_ones(), 0);
+	filter.clear();
+	assert!(filter.is_empty());
+	assert!(!filter.contains(&5));
+}
@@ -0,0 +1,57 @@
+use crate::*;
+
+#[test]
+fn insert_remove() {
+	let mut filter = CountingBloomFilter::new(1024, 4);
+	filter.insert(&1);
+	filter.insert(&2);
+	assert!(filter.contains(&1));
+	assert!(filter.remove(&1));
+	assert!(!filter.contains(&1));
+	assert!(filter.contains(&2));
+}
+
+#[test]
+fn remove_missing() {
+	let mut filter = CountingBloomFilter::new(1024, 4);
+	filter.insert(&1);
+	assert!(!filter.remove(&2));
+	assert!(filter.contains(&1));
+}
+
+#[test]
+fn duplicate_inserts() {
+	let mut filter = CountingBloomFilter::new(1024, 4);
+	filter.insert(&1);
+	filter.insert(&1);
+	assert!(filter.remove(&1));
+	assert!(filter.contains(&1));
+	assert!(filter.remove(&1));
+	assert!(filter.is_empty());
+}
+
+#[test]
+fn saturation() {
+	let mut filter = CountingBloomFilter::new(16, 1);
+	for _ in 0..300 {
+		filter.insert(&1);
+	}
+	for _ in 0..300 {
+		filter.remove(&1);
+	}
+	//Saturated counters can't know how many removals are safe, so they stay set.
+	assert!(filter.contains(&1));
+}
+
+#[test]
+fn union() {
+	let mut a = CountingBloomFilter::with_rate(100, 0.01);
+	let mut b = CountingBloomFilter::with_rate(100, 0.01);
+	a.insert(&"a");
+	b.insert(&"b");
+	a.union(&b);
+	assert!(a.contains(&"a"));
+	assert!(a.contains(&"b"));
+	assert!(a.remove(&"b"));
+	assert!(!a.contains(&"b"));
+}
@@ -0,0 +1,53 @@
+use crate::*;
+use std::slice;
+
+///Iterates over the leaves in order, keeping the path from the root to the current leaf.
+pub struct Iter<'a, T> {
+	pub(crate) stack: Vector<(&'a Branch<T>, usize)>,
+	pub(crate) leaf: slice::Iter<'a, T>,
+	pub(crate) remaining: usize,
+}
+
+impl<'a, T> Iter<'a, T> {
+	pub(crate) fn new(vec: &'a PersistentVector<T>) -> Self {
+		let mut iter = Iter {
+			stack: Vector::new(),
+			leaf: [].iter(),
+			remaining: vec.len,
+		};
+		match &vec.root {
+			Node::Leaf(leaf) => iter.leaf = leaf.as_slice().iter(),
+			Node::Branch(branch) => iter.stack.push((branch, 0)),
+		}
+		iter
+	}
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+	type Item = &'a T;
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			if let Some(elem) = self.leaf.next() {
+				self.remaining -= 1;
+				return Some(elem);
+			}
+			let (branch, idx) = self.stack.as_slice_mut().last_mut()?;
+			let branch: &'a Branch<T> = branch;
+			if *idx == branch.children.len() {
+				self.stack.pop();
+				continue;
+			}
+			*idx += 1;
+			match &branch.children[*idx - 1] {
+				Node::Leaf(leaf) => self.leaf = leaf.as_slice().iter(),
+				Node::Branch(child) => self.stack.push((child, 0)),
+			}
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(self.remaining, Some(self.remaining))
+	}
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
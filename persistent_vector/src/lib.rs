@@ -0,0 +1,285 @@
+use std::{fmt, fmt::Debug, iter::FromIterator, mem, ops::Index, sync::Arc};
+use vector::Vector;
+
+#[cfg(test)]
+pub mod test_i32;
+
+mod node;
+use node::{Branch, Node};
+
+pub mod iterator;
+use iterator::Iter;
+
+pub mod transient;
+pub use transient::TransientVector;
+
+//Small nodes in unit tests so a few hundred elements already build trees several levels deep.
+// The integration tests in `tests/` build without `cfg(test)` and cover the real width.
+#[cfg(not(test))]
+const BITS: usize = 5;
+#[cfg(test)]
+const BITS: usize = 2;
+const BRANCHING: usize = 1 << BITS;
+
+///An immutable vector built as a relaxed radix balanced (RRB) tree. Every operation returns a new
+/// version which shares all unchanged nodes with the old one, so keeping old versions around is
+/// cheap.
+///
+/// Elements are stored in leaves of up to 32 elements. Cloning is O(1), indexing, `push_back`,
+/// `update`, `split_at` and `concat` are O(log n).
+pub struct PersistentVector<T> {
+	pub(crate) root: Node<T>,
+	pub(crate) height: usize,
+	pub(crate) len: usize,
+}
+
+impl<T> Clone for PersistentVector<T> {
+	///Shares the whole tree, only the root's reference count is increased. Has complexity O(1).
+	fn clone(&self) -> Self {
+		PersistentVector {
+			root: self.root.clone(),
+			height: self.height,
+			len: self.len,
+		}
+	}
+}
+
+impl<T> Default for PersistentVector<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for PersistentVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T: PartialEq> PartialEq for PersistentVector<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+impl<T> Index<usize> for PersistentVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index).expect("Index was out of bounds")
+	}
+}
+
+impl<'a, T> IntoIterator for &'a PersistentVector<T> {
+	type Item = &'a T;
+	type IntoIter = Iter<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+impl<T> FromIterator<T> for PersistentVector<T> {
+	///Builds the tree bottom up from full leaves. Has O(n) complexity.
+	fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
+		let mut leaves = Vector::new();
+		let mut leaf = Vector::with_capacity(BRANCHING);
+		for elem in iter {
+			leaf.push(elem);
+			if leaf.len() == BRANCHING {
+				let full = mem::replace(&mut leaf, Vector::with_capacity(BRANCHING));
+				leaves.push(Node::Leaf(Arc::new(full)));
+			}
+		}
+		if !leaf.is_empty() {
+			leaves.push(Node::Leaf(Arc::new(leaf)));
+		}
+		Self::from_nodes(leaves)
+	}
+}
+
+impl<T> From<Vector<T>> for PersistentVector<T> {
+	fn from(vec: Vector<T>) -> Self {
+		vec.into_iter().collect()
+	}
+}
+
+impl<T: Clone> From<PersistentVector<T>> for Vector<T> {
+	fn from(vec: PersistentVector<T>) -> Self {
+		vec.to_vector()
+	}
+}
+
+impl<T> PersistentVector<T> {
+	///Creates a new empty vector. Does not allocate till elements are added.
+	pub fn new() -> Self {
+		PersistentVector {
+			root: Node::empty(),
+			height: 0,
+			len: 0,
+		}
+	}
+
+	//Groups full levels of nodes into branches till a single root is left.
+	fn from_nodes(mut nodes: Vector<Node<T>>) -> Self {
+		let mut height = 0;
+		while nodes.len() > 1 {
+			let mut parents = Vector::with_capacity(nodes.len().div_ceil(BRANCHING));
+			let mut children = Vector::with_capacity(BRANCHING);
+			for node in nodes {
+				children.push(node);
+				if children.len() == BRANCHING {
+					let full = mem::replace(&mut children, Vector::with_capacity(BRANCHING));
+					parents.push(Node::Branch(Arc::new(Branch::new(full))));
+				}
+			}
+			if !children.is_empty() {
+				parents.push(Node::Branch(Arc::new(Branch::new(children))));
+			}
+			nodes = parents;
+			height += 1;
+		}
+		match nodes.pop() {
+			Some(root) => Self::from_root(root, height),
+			None => Self::new(),
+		}
+	}
+
+	//Removes single child branches from the top, which splitting and concatenating leave behind.
+	fn from_root(mut root: Node<T>, mut height: usize) -> Self {
+		while let Node::Branch(branch) = &root {
+			if branch.children.len() != 1 {
+				break;
+			}
+			root = branch.children[0].clone();
+			height -= 1;
+		}
+		PersistentVector {
+			len: root.len(),
+			root,
+			height,
+		}
+	}
+
+	///Checks if the vector has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	///Returns the amount of elements stored in the vector.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	///Gets a reference to the element at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has complexity O(log n).
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		if idx >= self.len {
+			return None;
+		}
+		self.root.get(self.height, idx)
+	}
+
+	///Returns an iterator over borrowed elements of the vector. Walks the leaves in order, so
+	/// stepping is O(1) amortised.
+	pub fn iter(&self) -> Iter<'_, T> {
+		Iter::new(self)
+	}
+
+	///Creates a transient copy to apply a batch of changes to in place. Has complexity O(1).
+	pub fn transient(&self) -> TransientVector<T> {
+		TransientVector { vec: self.clone() }
+	}
+}
+
+impl<T: Clone> PersistentVector<T> {
+	///Returns a new version with `elem` inserted at the back.
+	///
+	/// Only the nodes along the rightmost path are copied. Has complexity O(log n).
+	pub fn push_back(&self, elem: T) -> Self {
+		let mut vec = self.clone();
+		vec.push_back_mut(elem);
+		vec
+	}
+
+	///Returns a new version with the element at `idx` replaced by `elem`.
+	///
+	/// Panics if idx is out of bounds. Only the nodes along the path to the element are copied.
+	/// Has complexity O(log n).
+	pub fn update(&self, idx: usize, elem: T) -> Self {
+		let mut vec = self.clone();
+		vec.set_mut(idx, elem);
+		vec
+	}
+
+	//Changes the nodes in place when they aren't shared with another version.
+	pub(crate) fn push_back_mut(&mut self, elem: T) {
+		if self.root.has_room() {
+			self.root.push(self.height, elem);
+		} else {
+			let old = mem::replace(&mut self.root, Node::empty());
+			let mut children = Vector::with_capacity(2);
+			children.push(old);
+			children.push(Node::single(self.height, elem));
+			self.root = Node::Branch(Arc::new(Branch::new(children)));
+			self.height += 1;
+		}
+		self.len += 1;
+	}
+
+	pub(crate) fn set_mut(&mut self, idx: usize, elem: T) {
+		assert!(idx < self.len, "Index was out of bounds");
+		self.root.set(self.height, idx, elem);
+	}
+
+	///Splits the vector into the elements before `idx` and the elements from `idx` onwards.
+	///
+	/// Panics if idx is greater than the length. Only the nodes along the path to `idx` are
+	/// copied. Has complexity O(log n).
+	pub fn split_at(&self, idx: usize) -> (Self, Self) {
+		assert!(idx <= self.len, "Index was out of bounds");
+		if idx == 0 {
+			return (Self::new(), self.clone());
+		}
+		if idx == self.len {
+			return (self.clone(), Self::new());
+		}
+		let (left, right) = self.root.split(self.height, idx);
+		(
+			Self::from_root(left.expect("Left side of a split is empty?"), self.height),
+			Self::from_root(right.expect("Right side of a split is empty?"), self.height),
+		)
+	}
+
+	///Returns a new vector with the elements of `self` followed by the elements of `other`.
+	///
+	/// Both trees are shared except for the nodes along the seam, which are rebalanced.
+	/// Has complexity O(log n).
+	pub fn concat(&self, other: &Self) -> Self {
+		if self.is_empty() {
+			return other.clone();
+		}
+		if other.is_empty() {
+			return self.clone();
+		}
+		let mut nodes = Node::concat(&self.root, self.height, &other.root, other.height);
+		let height = self.height.max(other.height);
+		if nodes.len() == 1 {
+			Self::from_root(
+				nodes.pop().expect("Concatenation returned no nodes?"),
+				height,
+			)
+		} else {
+			Self::from_root(Node::Branch(Arc::new(Branch::new(nodes))), height + 1)
+		}
+	}
+
+	///Copies the elements into a new `Vector`. Has O(n) complexity.
+	pub fn to_vector(&self) -> Vector<T> {
+		let mut vec = Vector::with_capacity(self.len);
+		for elem in self.iter() {
+			vec.push(elem.clone());
+		}
+		vec
+	}
+}
@@ -0,0 +1,363 @@
+use crate::*;
+
+///A node of the tree. Leaves hold up to `BRANCHING` elements and branches up to `BRANCHING`
+/// children, all leaves are at the same depth. Nodes are shared between versions and only
+/// copied when changed while shared.
+pub(crate) enum Node<T> {
+	Leaf(Arc<Vector<T>>),
+	Branch(Arc<Branch<T>>),
+}
+
+///The size table holds the cumulative element count up to and including each child, which
+/// allows children to be less than full (relaxed) wherever concatenation put them.
+pub(crate) struct Branch<T> {
+	pub(crate) children: Vector<Node<T>>,
+	pub(crate) sizes: Vector<usize>,
+}
+
+impl<T> Clone for Node<T> {
+	fn clone(&self) -> Self {
+		match self {
+			Node::Leaf(leaf) => Node::Leaf(Arc::clone(leaf)),
+			Node::Branch(branch) => Node::Branch(Arc::clone(branch)),
+		}
+	}
+}
+
+impl<T> Clone for Branch<T> {
+	fn clone(&self) -> Self {
+		Branch {
+			children: self.children.clone(),
+			sizes: self.sizes.clone(),
+		}
+	}
+}
+
+impl<T> Branch<T> {
+	pub(crate) fn new(children: Vector<Node<T>>) -> Self {
+		let mut sizes = Vector::with_capacity(children.len());
+		let mut total = 0;
+		for child in children.iter() {
+			total += child.len();
+			sizes.push(total);
+		}
+		Branch { children, sizes }
+	}
+
+	///Finds the child holding element `idx` and the index within that child.
+	///
+	/// Children hold at most `BRANCHING^height` elements, so the radix index is a lower bound
+	/// and the search only ever moves forward from it.
+	pub(crate) fn position(&self, height: usize, idx: usize) -> (usize, usize) {
+		let mut child = (idx >> (BITS * height)).min(self.children.len() - 1);
+		while self.sizes[child] <= idx {
+			child += 1;
+		}
+		let before = if child == 0 { 0 } else { self.sizes[child - 1] };
+		(child, idx - before)
+	}
+}
+
+impl<T> Node<T> {
+	pub(crate) fn empty() -> Self {
+		Node::Leaf(Arc::new(Vector::new()))
+	}
+
+	///Returns the amount of elements below this node.
+	pub(crate) fn len(&self) -> usize {
+		match self {
+			Node::Leaf(leaf) => leaf.len(),
+			Node::Branch(branch) => *branch.sizes.as_slice().last().unwrap_or(&0),
+		}
+	}
+
+	///Returns the amount of elements or children directly in this node.
+	pub(crate) fn slots(&self) -> usize {
+		match self {
+			Node::Leaf(leaf) => leaf.len(),
+			Node::Branch(branch) => branch.children.len(),
+		}
+	}
+
+	fn branch(&self) -> &Branch<T> {
+		match self {
+			Node::Branch(branch) => branch,
+			Node::Leaf(_) => unreachable!("Leaf above the bottom of the tree"),
+		}
+	}
+
+	fn leaf(&self) -> &Vector<T> {
+		match self {
+			Node::Leaf(leaf) => leaf,
+			Node::Branch(_) => unreachable!("Branch at the bottom of the tree"),
+		}
+	}
+
+	pub(crate) fn get(&self, height: usize, idx: usize) -> Option<&T> {
+		match self {
+			Node::Leaf(leaf) => leaf.get(idx),
+			Node::Branch(branch) => {
+				let (child, idx) = branch.position(height, idx);
+				branch.children[child].get(height - 1, idx)
+			}
+		}
+	}
+
+	///Creates a path of single child branches down to a leaf holding only `elem`.
+	pub(crate) fn single(height: usize, elem: T) -> Self {
+		let mut leaf = Vector::new();
+		leaf.push(elem);
+		let mut node = Node::Leaf(Arc::new(leaf));
+		for _ in 0..height {
+			let mut children = Vector::new();
+			children.push(node);
+			node = Node::Branch(Arc::new(Branch::new(children)));
+		}
+		node
+	}
+
+	///Checks if an element can be pushed to the back without adding a level.
+	pub(crate) fn has_room(&self) -> bool {
+		match self {
+			Node::Leaf(leaf) => leaf.len() < BRANCHING,
+			Node::Branch(branch) => {
+				branch.children.len() < BRANCHING
+					|| branch.children[branch.children.len() - 1].has_room()
+			}
+		}
+	}
+}
+
+impl<T: Clone> Node<T> {
+	///Pushes to the back of the rightmost path, copying the nodes along it that are shared.
+	/// `has_room` must have been checked first.
+	pub(crate) fn push(&mut self, height: usize, elem: T) {
+		match self {
+			Node::Leaf(leaf) => Arc::make_mut(leaf).push(elem),
+			Node::Branch(branch) => {
+				let branch = Arc::make_mut(branch);
+				let last = branch.children.len() - 1;
+				if branch.children[last].has_room() {
+					branch.children[last].push(height - 1, elem);
+					branch.sizes[last] += 1;
+				} else {
+					branch.children.push(Node::single(height - 1, elem));
+					let total = branch.sizes[last] + 1;
+					branch.sizes.push(total);
+				}
+			}
+		}
+	}
+
+	///Replaces the element at `idx`, copying the nodes along the path that are shared.
+	pub(crate) fn set(&mut self, height: usize, idx: usize, elem: T) {
+		match self {
+			Node::Leaf(leaf) => Arc::make_mut(leaf)[idx] = elem,
+			Node::Branch(branch) => {
+				let branch = Arc::make_mut(branch);
+				let (child, idx) = branch.position(height, idx);
+				branch.children[child].set(height - 1, idx, elem);
+			}
+		}
+	}
+
+	///Splits the node into the elements before `idx` and the rest. Only the nodes along the
+	/// path to `idx` are copied. Either side is `None` if it would be empty.
+	pub(crate) fn split(&self, height: usize, idx: usize) -> (Option<Node<T>>, Option<Node<T>>) {
+		match self {
+			Node::Leaf(leaf) => {
+				let (left, right) = leaf.as_slice().split_at(idx);
+				(leaf_from_slice(left), leaf_from_slice(right))
+			}
+			Node::Branch(branch) => {
+				let (child, offset) = branch.position(height, idx);
+				let (mid_left, mid_right) = branch.children[child].split(height - 1, offset);
+				let mut left = Vector::new();
+				for node in branch.children.iter().take(child) {
+					left.push(node.clone());
+				}
+				if let Some(node) = mid_left {
+					left.push(node);
+				}
+				let mut right = Vector::new();
+				if let Some(node) = mid_right {
+					right.push(node);
+				}
+				for node in branch.children.iter().skip(child + 1) {
+					right.push(node.clone());
+				}
+				(branch_from_children(left), branch_from_children(right))
+			}
+		}
+	}
+
+	///Concatenates two trees, returning one or two nodes at the height of the taller tree.
+	///
+	/// Descends the right edge of `left` and the left edge of `right` to the same height and
+	/// joins them there, then rebalances every level on the way back up so the nodes along the
+	/// seam don't stay underfull.
+	pub(crate) fn concat(left: &Node<T>, lh: usize, right: &Node<T>, rh: usize) -> Vector<Node<T>> {
+		if lh == 0 && rh == 0 {
+			let mut nodes = Vector::new();
+			if left.len() + right.len() <= BRANCHING {
+				let mut leaf = left.leaf().clone();
+				for elem in right.leaf().iter() {
+					leaf.push(elem.clone());
+				}
+				nodes.push(Node::Leaf(Arc::new(leaf)));
+			} else {
+				nodes.push(left.clone());
+				nodes.push(right.clone());
+			}
+			return nodes;
+		}
+
+		let mut children = Vector::new();
+		if lh > rh {
+			let left = left.branch();
+			let last = left.children.len() - 1;
+			let mid = Node::concat(&left.children[last], lh - 1, right, rh);
+			for node in left.children.iter().take(last) {
+				children.push(node.clone());
+			}
+			for node in mid {
+				children.push(node);
+			}
+		} else if lh < rh {
+			let right = right.branch();
+			let mid = Node::concat(left, lh, &right.children[0], rh - 1);
+			for node in mid {
+				children.push(node);
+			}
+			for node in right.children.iter().skip(1) {
+				children.push(node.clone());
+			}
+		} else {
+			let (left, right) = (left.branch(), right.branch());
+			let last = left.children.len() - 1;
+			let mid = Node::concat(&left.children[last], lh - 1, &right.children[0], rh - 1);
+			for node in left.children.iter().take(last) {
+				children.push(node.clone());
+			}
+			for node in mid {
+				children.push(node);
+			}
+			for node in right.children.iter().skip(1) {
+				children.push(node.clone());
+			}
+		}
+		rebalance(children, lh.max(rh) - 1)
+	}
+}
+
+//A level may have this many more nodes than strictly needed before it is rebalanced.
+const EXTRAS: usize = 2;
+
+///Redistributes `children` (nodes at `height`) so that they use at most `EXTRAS` more nodes than
+/// the minimum, then packs them into one or two branches.
+///
+/// Uses the concatenation plan from the RRB tree paper: the first node that isn't full is
+/// spread over the nodes after it, removing one node, until there are few enough left. Nodes
+/// that keep their contents are shared rather than copied.
+fn rebalance<T: Clone>(children: Vector<Node<T>>, height: usize) -> Vector<Node<T>> {
+	let mut plan: Vector<usize> = children.iter().map(Node::slots).collect();
+	let total: usize = plan.iter().sum();
+	let optimal = total.div_ceil(BRANCHING);
+	let mut len = plan.len();
+	let mut i = 0;
+	while len > optimal + EXTRAS {
+		while plan[i] >= BRANCHING {
+			i += 1;
+		}
+		let mut remaining = plan[i];
+		while remaining > 0 {
+			let size = (remaining + plan[i + 1]).min(BRANCHING);
+			remaining = remaining + plan[i + 1] - size;
+			plan[i] = size;
+			i += 1;
+		}
+		for j in i..len - 1 {
+			plan[j] = plan[j + 1];
+		}
+		len -= 1;
+		i = i.saturating_sub(1);
+	}
+
+	let mut nodes = Vector::with_capacity(len);
+	let (mut child, mut offset) = (0, 0);
+	for &size in plan.iter().take(len) {
+		if offset == 0 && children[child].slots() == size {
+			nodes.push(children[child].clone());
+			child += 1;
+			continue;
+		}
+		if height == 0 {
+			let mut leaf = Vector::with_capacity(size);
+			while leaf.len() < size {
+				let from = children[child].leaf();
+				let take = (size - leaf.len()).min(from.len() - offset);
+				for elem in from.as_slice()[offset..offset + take].iter() {
+					leaf.push(elem.clone());
+				}
+				offset += take;
+				if offset == from.len() {
+					child += 1;
+					offset = 0;
+				}
+			}
+			nodes.push(Node::Leaf(Arc::new(leaf)));
+		} else {
+			let mut grandchildren = Vector::with_capacity(size);
+			while grandchildren.len() < size {
+				let from = &children[child].branch().children;
+				let take = (size - grandchildren.len()).min(from.len() - offset);
+				for node in from.as_slice()[offset..offset + take].iter() {
+					grandchildren.push(node.clone());
+				}
+				offset += take;
+				if offset == from.len() {
+					child += 1;
+					offset = 0;
+				}
+			}
+			nodes.push(Node::Branch(Arc::new(Branch::new(grandchildren))));
+		}
+	}
+
+	let mut branches = Vector::new();
+	if nodes.len() <= BRANCHING {
+		branches.push(Node::Branch(Arc::new(Branch::new(nodes))));
+	} else {
+		let mut first = Vector::with_capacity(BRANCHING);
+		let mut second = Vector::with_capacity(nodes.len() - BRANCHING);
+		for (i, node) in nodes.into_iter().enumerate() {
+			if i < BRANCHING {
+				first.push(node);
+			} else {
+				second.push(node);
+			}
+		}
+		branches.push(Node::Branch(Arc::new(Branch::new(first))));
+		branches.push(Node::Branch(Arc::new(Branch::new(second))));
+	}
+	branches
+}
+
+fn leaf_from_slice<T: Clone>(elems: &[T]) -> Option<Node<T>> {
+	if elems.is_empty() {
+		return None;
+	}
+	let mut leaf = Vector::with_capacity(elems.len());
+	for elem in elems {
+		leaf.push(elem.clone());
+	}
+	Some(Node::Leaf(Arc::new(leaf)))
+}
+
+fn branch_from_children<T>(children: Vector<Node<T>>) -> Option<Node<T>> {
+	if children.is_empty() {
+		None
+	} else {
+		Some(Node::Branch(Arc::new(Branch::new(children))))
+	}
+}
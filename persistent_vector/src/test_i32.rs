@@ -0,0 +1,173 @@
+use crate::*;
+
+//Checks both indexing and iteration against the expected elements.
+fn check(vec: &PersistentVector<i32>, expected: &[i32]) {
+	assert_eq!(vec.len(), expected.len());
+	for (i, elem) in expected.iter().enumerate() {
+		assert_eq!(vec.get(i), Some(elem), "at index {}", i);
+	}
+	assert_eq!(vec.get(expected.len()), None);
+	assert!(vec.iter().eq(expected.iter()));
+	assert_eq!(vec.iter().len(), expected.len());
+}
+
+fn range(start: i32, end: i32) -> Vector<i32> {
+	(start..end).collect()
+}
+
+#[test]
+fn push_back() {
+	let mut vec = PersistentVector::new();
+	for i in 0..300 {
+		vec = vec.push_back(i);
+	}
+	check(&vec, range(0, 300).as_slice());
+}
+
+#[test]
+fn old_versions_unchanged() {
+	let a: PersistentVector<i32> = (0..100).collect();
+	let b = a.push_back(100);
+	let c = a.update(50, -1);
+	check(&a, range(0, 100).as_slice());
+	check(&b, range(0, 101).as_slice());
+	let mut expected = range(0, 100);
+	expected[50] = -1;
+	check(&c, expected.as_slice());
+}
+
+#[test]
+fn collect_and_convert() {
+	let empty: PersistentVector<i32> = Vector::new().into();
+	check(&empty, &[]);
+	for len in [1, 4, 5, 16, 17, 64, 65, 200] {
+		let vec: PersistentVector<i32> = range(0, len).into();
+		check(&vec, range(0, len).as_slice());
+		let back: Vector<i32> = vec.into();
+		assert_eq!(back.as_slice(), range(0, len).as_slice());
+	}
+}
+
+#[test]
+fn split_at() {
+	let vec: PersistentVector<i32> = (0..150).collect();
+	for idx in 0..=150 {
+		let (left, right) = vec.split_at(idx);
+		check(&left, range(0, idx as i32).as_slice());
+		check(&right, range(idx as i32, 150).as_slice());
+	}
+}
+
+#[test]
+fn concat() {
+	for left_len in [0, 1, 3, 4, 17, 70] {
+		for right_len in [0, 1, 2, 5, 16, 90] {
+			let left: PersistentVector<i32> = (0..left_len).collect();
+			let right: PersistentVector<i32> = (left_len..left_len + right_len).collect();
+			let vec = left.concat(&right);
+			check(&vec, range(0, left_len + right_len).as_slice());
+			check(
+				&vec.push_back(-1),
+				{
+					let mut expected = range(0, left_len + right_len);
+					expected.push(-1);
+					expected
+				}
+				.as_slice(),
+			);
+		}
+	}
+}
+
+#[test]
+fn random_operations() {
+	let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+	let mut random = move |max: usize| {
+		seed ^= seed << 13;
+		seed ^= seed >> 7;
+		seed ^= seed << 17;
+		(seed % max as u64) as usize
+	};
+
+	let mut vec = PersistentVector::new();
+	let mut expected = Vector::new();
+	for step in 0..400 {
+		match random(5) {
+			0 => {
+				vec = vec.push_back(step);
+				expected.push(step);
+			}
+			1 if !expected.is_empty() => {
+				let idx = random(expected.len());
+				vec = vec.update(idx, -step);
+				expected[idx] = -step;
+			}
+			2 => {
+				//Splits and glues back together, rebalancing along a random seam.
+				let idx = random(expected.len() + 1);
+				let (left, right) = vec.split_at(idx);
+				vec = left.concat(&right);
+			}
+			3 => {
+				//Cuts out a random range, joining two unrelated seams.
+				let start = random(expected.len() + 1);
+				let end = start + random(expected.len() - start + 1);
+				let (left, rest) = vec.split_at(start);
+				let (_, right) = rest.split_at(end - start);
+				vec = left.concat(&right);
+				let mut kept = Vector::new();
+				for (i, &elem) in expected.iter().enumerate() {
+					if i < start || i >= end {
+						kept.push(elem);
+					}
+				}
+				expected = kept;
+			}
+			_ => {
+				let len = random(40) as i32;
+				let other: PersistentVector<i32> = (0..len).collect();
+				vec = vec.concat(&other);
+				for i in 0..len {
+					expected.push(i);
+				}
+			}
+		}
+		check(&vec, expected.as_slice());
+	}
+}
+
+#[test]
+fn transient() {
+	let base: PersistentVector<i32> = (0..20).collect();
+	let mut transient = base.transient();
+	for i in 20..100 {
+		transient.push_back(i);
+	}
+	transient.set(0, -1);
+	assert_eq!(transient.len(), 100);
+	assert_eq!(transient.get(0), Some(&-1));
+	let vec = transient.persistent();
+	let mut expected = range(0, 100);
+	expected[0] = -1;
+	check(&vec, expected.as_slice());
+	check(&base, range(0, 20).as_slice());
+}
+
+#[test]
+fn equality_and_debug() {
+	let a: PersistentVector<i32> = (0..3).collect();
+	let b = PersistentVector::new()
+		.push_back(0)
+		.push_back(1)
+		.push_back(2);
+	assert_eq!(a, b);
+	assert_ne!(a, b.update(1, 5));
+	assert_eq!(format!("{:?}", a), "[0, 1, 2]");
+}
+
+#[test]
+#[should_panic]
+fn update_out_of_bounds() {
+	let vec: PersistentVector<i32> = (0..3).collect();
+	vec.update(3, 0);
+}
@@ -0,0 +1,57 @@
+use crate::*;
+
+///A mutable builder over a `PersistentVector` for applying many changes at once.
+///
+/// Nodes still shared with the vector it was made from are copied on their first change, after
+/// which they belong to the transient alone and are changed in place. Turn it back with
+/// `persistent` once done.
+pub struct TransientVector<T> {
+	pub(crate) vec: PersistentVector<T>,
+}
+
+impl<T> Default for TransientVector<T> {
+	fn default() -> Self {
+		PersistentVector::new().transient()
+	}
+}
+
+impl<T> TransientVector<T> {
+	///Checks if the vector has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.vec.is_empty()
+	}
+
+	///Returns the amount of elements stored in the vector.
+	pub fn len(&self) -> usize {
+		self.vec.len()
+	}
+
+	///Gets a reference to the element at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has complexity O(log n).
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		self.vec.get(idx)
+	}
+
+	///Turns the builder back into an immutable vector. Has complexity O(1).
+	pub fn persistent(self) -> PersistentVector<T> {
+		self.vec
+	}
+}
+
+impl<T: Clone> TransientVector<T> {
+	///Inserts an element at the back of the vector.
+	///
+	/// Has complexity O(log n), but only copies nodes the first time they're changed.
+	pub fn push_back(&mut self, elem: T) {
+		self.vec.push_back_mut(elem);
+	}
+
+	///Replaces the element at `idx` with `elem`.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(log n), but only copies nodes the first
+	/// time they're changed.
+	pub fn set(&mut self, idx: usize, elem: T) {
+		self.vec.set_mut(idx, elem);
+	}
+}
@@ -0,0 +1,128 @@
+//The unit tests shrink nodes to 4 elements. These run against the library as shipped, with
+// nodes of 32, so the trees and size tables here are the real ones.
+use persistent_vector::PersistentVector;
+
+const WIDTH: usize = 32;
+
+//Checks the length, every element through iteration and a spread of them through indexing,
+// including both sides of every leaf boundary.
+fn check(vec: &PersistentVector<usize>, expected: impl Iterator<Item = usize> + Clone) {
+	let len = expected.clone().count();
+	assert_eq!(vec.len(), len);
+	assert!(vec.iter().copied().eq(expected.clone()));
+	let step = (len / 4096).max(1);
+	for (i, elem) in expected.enumerate() {
+		if i % step == 0 || i % WIDTH == 0 || i % WIDTH == WIDTH - 1 || i + 1 == len {
+			assert_eq!(vec.get(i), Some(&elem), "at index {}", i);
+		}
+	}
+	assert_eq!(vec.get(len), None);
+}
+
+fn build(start: usize, end: usize) -> PersistentVector<usize> {
+	let mut transient = PersistentVector::new().transient();
+	for i in start..end {
+		transient.push_back(i);
+	}
+	transient.persistent()
+}
+
+#[test]
+fn push_past_each_level() {
+	for level in 2..=4 {
+		let full = WIDTH.pow(level);
+		//Persistent pushes across the point where the tree grows a level.
+		let mut vec: PersistentVector<usize> = (0..full - 2).collect();
+		for i in full - 2..full + 3 {
+			vec = vec.push_back(i);
+		}
+		check(&vec, 0..full + 3);
+		let updated = vec.update(full, 0);
+		assert_eq!(updated[full], 0);
+		assert_eq!(vec[full], full);
+	}
+	check(&build(0, WIDTH.pow(4) + 1), 0..WIDTH.pow(4) + 1);
+}
+
+#[test]
+fn split_and_concat() {
+	for &len in &[WIDTH.pow(2) + 5, WIDTH.pow(3) + 7] {
+		let vec = build(0, len);
+		for &idx in &[
+			0,
+			1,
+			WIDTH - 1,
+			WIDTH,
+			WIDTH + 1,
+			len / 3,
+			len / 2 + 13,
+			len - 1,
+			len,
+		] {
+			let (left, right) = vec.split_at(idx);
+			check(&left, 0..idx);
+			check(&right, idx..len);
+			check(&left.concat(&right), 0..len);
+			//Reversed halves join leaves that didn't border each other.
+			check(&right.concat(&left), (idx..len).chain(0..idx));
+		}
+	}
+}
+
+#[test]
+fn concat_relaxed_trees() {
+	//Uneven pieces leave partly filled leaves at every seam, so the result needs size tables.
+	let mut vec = PersistentVector::new();
+	let mut start = 0;
+	for piece in 0..800 {
+		let len = (piece * 7919) % 3001;
+		vec = vec.concat(&build(start, start + len));
+		start += len;
+	}
+	assert!(start > WIDTH.pow(4));
+	check(&vec, 0..start);
+
+	//Splitting inside the relaxed tree and joining again keeps every element in place.
+	let (left, right) = vec.split_at(start / 3 + 17);
+	let (middle, right) = right.split_at(start / 3);
+	check(&middle, start / 3 + 17..2 * (start / 3) + 17);
+	check(&left.concat(&middle).concat(&right), 0..start);
+}
+
+#[test]
+fn random_operations() {
+	let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+	let mut random = move |max: usize| {
+		seed ^= seed << 13;
+		seed ^= seed >> 7;
+		seed ^= seed << 17;
+		(seed % max as u64) as usize
+	};
+
+	let mut vec = PersistentVector::new();
+	let mut expected: Vec<usize> = Vec::new();
+	for step in 0..300 {
+		match random(3) {
+			0 => {
+				let len = random(5000);
+				vec = vec.concat(&build(step * 10_000, step * 10_000 + len));
+				expected.extend(step * 10_000..step * 10_000 + len);
+			}
+			1 => {
+				let start = random(expected.len() + 1);
+				let end = start + random(expected.len() - start + 1);
+				let (left, rest) = vec.split_at(start);
+				let (_, right) = rest.split_at(end - start);
+				vec = left.concat(&right);
+				expected.drain(start..end);
+			}
+			_ if !expected.is_empty() => {
+				let idx = random(expected.len());
+				vec = vec.update(idx, step);
+				expected[idx] = step;
+			}
+			_ => {}
+		}
+		check(&vec, expected.iter().copied());
+	}
+}
@@ -0,0 +1,57 @@
+use crate::*;
+use std::error::Error;
+
+///The error returned when converting bytes that aren't valid UTF-8 into a `VString`.
+/// Holds on to the bytes so they aren't lost.
+pub struct FromUtf8Error {
+	pub(crate) bytes: Vector<u8>,
+	pub(crate) valid_up_to: usize,
+	pub(crate) error_len: Option<usize>,
+}
+
+impl FromUtf8Error {
+	///Returns the bytes that failed to convert.
+	pub fn into_bytes(self) -> Vector<u8> {
+		self.bytes
+	}
+
+	///Borrows the bytes that failed to convert.
+	pub fn as_bytes(&self) -> &[u8] {
+		self.bytes.as_slice()
+	}
+
+	///Returns the length of the valid UTF-8 prefix of the bytes.
+	pub fn valid_up_to(&self) -> usize {
+		self.valid_up_to
+	}
+
+	///Returns the length of the invalid sequence starting at `valid_up_to`, or `None` if the
+	/// bytes ended in the middle of a character that might have been valid with more input.
+	pub fn error_len(&self) -> Option<usize> {
+		self.error_len
+	}
+}
+
+impl fmt::Debug for FromUtf8Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("FromUtf8Error")
+			.field("valid_up_to", &self.valid_up_to)
+			.field("error_len", &self.error_len)
+			.finish()
+	}
+}
+
+impl fmt::Display for FromUtf8Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.error_len {
+			Some(len) => write!(
+				f,
+				"Invalid UTF-8 sequence of {} bytes at index {}",
+				len, self.valid_up_to
+			),
+			None => write!(f, "Incomplete UTF-8 sequence at index {}", self.valid_up_to),
+		}
+	}
+}
+
+impl Error for FromUtf8Error {}
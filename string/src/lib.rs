@@ -0,0 +1,277 @@
+use std::{
+	fmt,
+	iter::FromIterator,
+	ops::{Deref, DerefMut},
+	str,
+};
+use vector::Vector;
+
+#[cfg(test)]
+pub mod test_string;
+
+pub mod error;
+use error::FromUtf8Error;
+
+///An owned, growable UTF-8 string stored in a `Vector<u8>`. Does not allocate upon creation.
+///
+/// Every method keeps the bytes valid UTF-8, which is what makes dereferencing to `str` safe.
+pub struct VString {
+	pub(crate) vec: Vector<u8>,
+}
+
+impl Default for VString {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl fmt::Debug for VString {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&**self, f)
+	}
+}
+
+impl fmt::Display for VString {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&**self, f)
+	}
+}
+
+impl fmt::Write for VString {
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		self.push_str(s);
+		Ok(())
+	}
+
+	fn write_char(&mut self, c: char) -> fmt::Result {
+		self.push(c);
+		Ok(())
+	}
+}
+
+impl Deref for VString {
+	type Target = str;
+
+	fn deref(&self) -> &Self::Target {
+		//Safety: The bytes are kept valid UTF-8 by every method that changes them.
+		unsafe { str::from_utf8_unchecked(self.vec.as_slice()) }
+	}
+}
+
+impl DerefMut for VString {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		//Safety: The bytes are kept valid UTF-8 by every method that changes them, and
+		// `str` itself only allows changes that keep it valid.
+		unsafe { str::from_utf8_unchecked_mut(self.vec.as_slice_mut()) }
+	}
+}
+
+impl PartialEq for VString {
+	fn eq(&self, other: &Self) -> bool {
+		**self == **other
+	}
+}
+
+impl Eq for VString {}
+
+impl PartialEq<str> for VString {
+	fn eq(&self, other: &str) -> bool {
+		&**self == other
+	}
+}
+
+impl PartialEq<&str> for VString {
+	fn eq(&self, other: &&str) -> bool {
+		&**self == *other
+	}
+}
+
+impl From<&str> for VString {
+	fn from(s: &str) -> Self {
+		let mut string = VString::with_capacity(s.len());
+		string.push_str(s);
+		string
+	}
+}
+
+impl From<VString> for Vector<u8> {
+	fn from(s: VString) -> Self {
+		s.into_bytes()
+	}
+}
+
+impl FromIterator<char> for VString {
+	fn from_iter<A: IntoIterator<Item = char>>(iter: A) -> Self {
+		let iter = iter.into_iter();
+		let (min, _) = iter.size_hint();
+		let mut string = VString::with_capacity(min);
+		for c in iter {
+			string.push(c);
+		}
+		string
+	}
+}
+
+impl<'a> FromIterator<&'a str> for VString {
+	fn from_iter<A: IntoIterator<Item = &'a str>>(iter: A) -> Self {
+		let mut string = VString::new();
+		for s in iter {
+			string.push_str(s);
+		}
+		string
+	}
+}
+
+impl VString {
+	///Creates a new empty string. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		VString { vec: Vector::new() }
+	}
+
+	///Creates a new string with a preallocated buffer with space for `cap` bytes.
+	pub fn with_capacity(cap: usize) -> Self {
+		VString {
+			vec: Vector::with_capacity(cap),
+		}
+	}
+
+	///Converts a vector of bytes into a string, checking that it is valid UTF-8.
+	///
+	/// Returns an error describing where the invalid sequence starts and how long it is, which
+	/// also gives the vector back. Has O(n) complexity.
+	pub fn from_utf8(vec: Vector<u8>) -> Result<Self, FromUtf8Error> {
+		match str::from_utf8(vec.as_slice()) {
+			Ok(_) => Ok(VString { vec }),
+			Err(error) => Err(FromUtf8Error {
+				bytes: vec,
+				valid_up_to: error.valid_up_to(),
+				error_len: error.error_len(),
+			}),
+		}
+	}
+
+	///Converts bytes into a string, replacing every invalid sequence with `U+FFFD REPLACEMENT CHARACTER`.
+	///
+	/// Has O(n) complexity.
+	pub fn from_utf8_lossy(bytes: &[u8]) -> Self {
+		let mut string = VString::with_capacity(bytes.len());
+		for chunk in bytes.utf8_chunks() {
+			string.push_str(chunk.valid());
+			if !chunk.invalid().is_empty() {
+				string.push(char::REPLACEMENT_CHARACTER);
+			}
+		}
+		string
+	}
+
+	///Converts a vector of bytes into a string without checking that it is valid UTF-8.
+	///
+	/// Has complexity O(1).
+	/// # Safety
+	/// The bytes must be valid UTF-8, every other method assumes this.
+	pub unsafe fn from_utf8_unchecked(vec: Vector<u8>) -> Self {
+		VString { vec }
+	}
+
+	///Returns the underlying bytes.
+	///
+	/// Has complexity O(1).
+	pub fn into_bytes(self) -> Vector<u8> {
+		self.vec
+	}
+
+	///Borrows the string as a `str`.
+	///
+	/// Has complexity O(1).
+	pub fn as_str(&self) -> &str {
+		self
+	}
+
+	///Borrows the string as a mutable `str`.
+	///
+	/// Has complexity O(1).
+	pub fn as_mut_str(&mut self) -> &mut str {
+		self
+	}
+
+	///Appends a character to the end of the string.
+	///
+	/// Has complexity O(1).
+	pub fn push(&mut self, c: char) {
+		let mut buf = [0; 4];
+		self.push_str(c.encode_utf8(&mut buf));
+	}
+
+	///Appends a string slice to the end of the string.
+	///
+	/// Has O(n) complexity in the length of `s`.
+	pub fn push_str(&mut self, s: &str) {
+		self.vec.extend_from_slice(s.as_bytes());
+	}
+
+	///Removes the last character of the string.
+	///
+	/// Returns `None` if the string is empty. Has complexity O(1).
+	pub fn pop(&mut self) -> Option<char> {
+		let c = self.chars().next_back()?;
+		for _ in 0..c.len_utf8() {
+			self.vec.pop();
+		}
+		Some(c)
+	}
+
+	///Inserts a character at byte index `idx`, moving everything after it to the right.
+	///
+	/// Panics if `idx` is not on a character boundary. Has O(n) complexity.
+	pub fn insert(&mut self, idx: usize, c: char) {
+		assert!(
+			self.is_char_boundary(idx),
+			"Index is not on a character boundary"
+		);
+		let mut buf = [0; 4];
+		let bytes = c.encode_utf8(&mut buf).as_bytes();
+		//Appends the bytes and rotates them into place, shifting the tail once.
+		self.vec.extend_from_slice(bytes);
+		self.vec.as_slice_mut()[idx..].rotate_right(bytes.len());
+	}
+
+	///Removes the character starting at byte index `idx`, moving everything after it to the left.
+	///
+	/// Panics if `idx` is out of bounds or not on a character boundary. Has O(n) complexity.
+	pub fn remove(&mut self, idx: usize) -> char {
+		let c = self[idx..]
+			.chars()
+			.next()
+			.expect("Index was out of bounds!");
+		//Rotates the character's bytes to the end, shifting the tail once, and cuts them off.
+		let len = self.len() - c.len_utf8();
+		self.vec.as_slice_mut()[idx..].rotate_left(c.len_utf8());
+		//Safety: Shortens the string by exactly the moved character, bytes don't need to be
+		// dropped.
+		unsafe { self.vec.set_len(len) };
+		c
+	}
+
+	///Shortens the string to `len` bytes. Does nothing if the string is already shorter.
+	///
+	/// Panics if `len` is not on a character boundary. Has O(1) complexity.
+	pub fn truncate(&mut self, len: usize) {
+		if len >= self.len() {
+			return;
+		}
+		assert!(
+			self.is_char_boundary(len),
+			"Length is not on a character boundary"
+		);
+		//Safety: `len` is shorter than the current length and on a character boundary, bytes
+		// don't need to be dropped.
+		unsafe { self.vec.set_len(len) };
+	}
+
+	///Removes every character in the string.
+	///
+	/// Has O(1) complexity.
+	pub fn clear(&mut self) {
+		self.truncate(0);
+	}
+}
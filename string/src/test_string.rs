@@ -0,0 +1,108 @@
+use crate::*;
+use std::fmt::Write;
+
+#[test]
+fn push() {
+	let mut s = VString::new();
+	s.push('h');
+	s.push_str("ello ");
+	s.push('世');
+	assert_eq!(s, "hello 世");
+	assert_eq!(s.len(), 9);
+	assert_eq!(s.pop(), Some('世'));
+	assert_eq!(s, "hello ");
+}
+
+#[test]
+fn insert_remove() {
+	let mut s = VString::from("aöc");
+	s.insert(3, '€');
+	assert_eq!(s, "aö€c");
+	assert_eq!(s.remove(1), 'ö');
+	assert_eq!(s, "a€c");
+	assert_eq!(s.remove(4), 'c');
+	assert_eq!(s, "a€");
+}
+
+#[test]
+fn insert_remove_ends() {
+	let mut s = VString::new();
+	s.insert(0, '€');
+	s.insert(0, 'a');
+	s.insert(4, '世');
+	s.push_str(&"ö".repeat(100));
+	assert_eq!(s.len(), 1 + 3 + 3 + 200);
+	assert_eq!(s.remove(0), 'a');
+	assert_eq!(s.remove(3), '世');
+	assert_eq!(s.remove(s.len() - 2), 'ö');
+	assert_eq!(s.remove(0), '€');
+	assert_eq!(s, "ö".repeat(99).as_str());
+}
+
+#[test]
+#[should_panic]
+fn insert_not_boundary() {
+	let mut s = VString::from("ö");
+	s.insert(1, 'a');
+}
+
+#[test]
+fn deref() {
+	let mut s = VString::from("Hello, World");
+	assert!(s.starts_with("Hello"));
+	assert_eq!(s.split(", ").count(), 2);
+	s.make_ascii_uppercase();
+	assert_eq!(s.as_str(), "HELLO, WORLD");
+}
+
+#[test]
+fn write() {
+	let mut s = VString::new();
+	write!(s, "{}-{:?}", 1, "two").unwrap();
+	assert_eq!(s, "1-\"two\"");
+	assert_eq!(format!("{}", s), "1-\"two\"");
+}
+
+#[test]
+fn from_iter() {
+	let s: VString = "abc".chars().rev().collect();
+	assert_eq!(s, "cba");
+	let s: VString = vec!["ab", "cd"].into_iter().collect();
+	assert_eq!(s, "abcd");
+}
+
+#[test]
+fn from_utf8() {
+	let bytes: Vector<u8> = "añb".bytes().collect();
+	assert_eq!(VString::from_utf8(bytes).unwrap(), "añb");
+
+	let bytes: Vector<u8> = vec![b'a', 0xff, b'b'].into_iter().collect();
+	let error = VString::from_utf8(bytes).unwrap_err();
+	assert_eq!(error.valid_up_to(), 1);
+	assert_eq!(error.error_len(), Some(1));
+	assert_eq!(
+		error.to_string(),
+		"Invalid UTF-8 sequence of 1 bytes at index 1"
+	);
+	assert_eq!(error.into_bytes().as_slice(), &[b'a', 0xff, b'b']);
+
+	let bytes: Vector<u8> = "ñ".bytes().take(1).collect();
+	let error = VString::from_utf8(bytes).unwrap_err();
+	assert_eq!(error.valid_up_to(), 0);
+	assert_eq!(error.error_len(), None);
+}
+
+#[test]
+fn from_utf8_lossy() {
+	let s = VString::from_utf8_lossy(b"a\xffb\xe2\x82");
+	assert_eq!(s, "a\u{FFFD}b\u{FFFD}");
+}
+
+#[test]
+fn truncate() {
+	let mut s = VString::from("abcö");
+	s.truncate(3);
+	assert_eq!(s, "abc");
+	s.clear();
+	assert!(s.is_empty());
+}
@@ -0,0 +1,227 @@
+use crate::*;
+use std::{
+	cell::UnsafeCell,
+	mem::MaybeUninit,
+	sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+///Segment `k` holds `FIRST_SEGMENT << k` slots, so every index fits in one of `SEGMENTS` segments.
+const FIRST_SEGMENT_BITS: u32 = 5;
+const FIRST_SEGMENT: usize = 1 << FIRST_SEGMENT_BITS;
+const SEGMENTS: usize = (usize::BITS - FIRST_SEGMENT_BITS) as usize;
+
+pub(crate) struct Slot<T> {
+	ready: AtomicBool,
+	value: UnsafeCell<MaybeUninit<T>>,
+}
+
+///An append-only vector that many threads can push to at once through a shared reference.
+///
+/// Elements live in segments of doubling size which are never moved or freed while the vector
+/// is alive, so references handed out by `get` stay valid while other threads keep pushing.
+/// Pushing only takes an atomic increment, plus an allocation for the first push into each
+/// segment.
+pub struct ConcurrentVector<T> {
+	pub(crate) segments: [AtomicPtr<Slot<T>>; SEGMENTS],
+	pub(crate) reserved: AtomicUsize,
+}
+
+//Safety: Pushing moves `T`s in from other threads and `get` shares them between threads.
+unsafe impl<T: Send> Send for ConcurrentVector<T> {}
+unsafe impl<T: Send + Sync> Sync for ConcurrentVector<T> {}
+
+impl<T> Default for ConcurrentVector<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for ConcurrentVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T> Index<usize> for ConcurrentVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index)
+			.expect("Index was out of bounds or not yet written")
+	}
+}
+
+impl<T> FromIterator<T> for ConcurrentVector<T> {
+	fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
+		let vec = ConcurrentVector::new();
+		for elem in iter {
+			vec.push(elem);
+		}
+		vec
+	}
+}
+
+impl<T> Drop for ConcurrentVector<T> {
+	fn drop(&mut self) {
+		let len = *self.reserved.get_mut();
+		for (k, segment) in self.segments.iter_mut().enumerate() {
+			let ptr = *segment.get_mut();
+			if ptr.is_null() {
+				continue;
+			}
+			let start = (FIRST_SEGMENT << k) - FIRST_SEGMENT;
+			let used = len.saturating_sub(start).min(FIRST_SEGMENT << k);
+			for i in 0..used {
+				//Safety: The segment holds `FIRST_SEGMENT << k` slots and no other thread can
+				// access them during drop. Only slots marked ready have been written.
+				unsafe {
+					let slot = &mut *ptr.add(i);
+					if *slot.ready.get_mut() {
+						ptr::drop_in_place(slot.value.get_mut().as_mut_ptr());
+					}
+				}
+			}
+			//Safety: Allocated in `segment` with the same layout.
+			unsafe { alloc::dealloc(ptr as *mut u8, Self::segment_layout(k)) };
+		}
+	}
+}
+
+impl<T> ConcurrentVector<T> {
+	///Creates a new empty vector. Does not allocate till the first push.
+	pub fn new() -> Self {
+		ConcurrentVector {
+			segments: [(); SEGMENTS].map(|_| AtomicPtr::new(ptr::null_mut())),
+			reserved: AtomicUsize::new(0),
+		}
+	}
+
+	///Returns the segment and the position within it of `idx`.
+	fn locate(idx: usize) -> (usize, usize) {
+		let shifted = idx + FIRST_SEGMENT;
+		let high_bit = (usize::BITS - 1 - shifted.leading_zeros()) as usize;
+		(
+			high_bit - FIRST_SEGMENT_BITS as usize,
+			shifted - (1 << high_bit),
+		)
+	}
+
+	fn segment_layout(k: usize) -> Layout {
+		Layout::array::<Slot<T>>(FIRST_SEGMENT << k).expect("Overflow")
+	}
+
+	///Returns segment `k`, allocating it if no thread has yet. When two threads race to allocate
+	/// the same segment the loser frees its allocation and uses the winner's.
+	fn segment(&self, k: usize) -> *mut Slot<T> {
+		let ptr = self.segments[k].load(Ordering::Acquire);
+		if !ptr.is_null() {
+			return ptr;
+		}
+		let layout = Self::segment_layout(k);
+		//Safety: The layout is never zero sized as every slot holds a flag. Zeroed memory is
+		// a valid `Slot` with `ready` unset.
+		let new = unsafe { alloc::alloc_zeroed(layout) as *mut Slot<T> };
+		if new.is_null() {
+			alloc::handle_alloc_error(layout);
+		}
+		match self.segments[k].compare_exchange(
+			ptr::null_mut(),
+			new,
+			Ordering::AcqRel,
+			Ordering::Acquire,
+		) {
+			Ok(_) => new,
+			Err(existing) => {
+				//Safety: The allocation was never shared.
+				unsafe { alloc::dealloc(new as *mut u8, layout) };
+				existing
+			}
+		}
+	}
+
+	///Returns the amount of elements pushed, including those still being written by other
+	/// threads.
+	pub fn len(&self) -> usize {
+		self.reserved.load(Ordering::Acquire)
+	}
+
+	///Checks if nothing has been pushed to the vector.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	///Inserts an element at the back of the vector and returns its index. Can be called from
+	/// many threads at once.
+	///
+	/// Panics if the length of the vector is equal to usize::MAX. Has complexity O(1).
+	pub fn push(&self, elem: T) -> usize {
+		let idx = self.reserved.fetch_add(1, Ordering::AcqRel);
+		assert!(idx <= usize::MAX - FIRST_SEGMENT, "Overflow");
+		let (k, offset) = Self::locate(idx);
+		//Safety: The index was reserved by this thread alone, so no one else writes the slot.
+		// Readers only look at the value once `ready` is set, which is released after the write.
+		unsafe {
+			let slot = &*self.segment(k).add(offset);
+			(*slot.value.get()).as_mut_ptr().write(elem);
+			slot.ready.store(true, Ordering::Release);
+		}
+		idx
+	}
+
+	///Gets a reference to the element at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector or another thread is still
+	/// writing the element. Has complexity O(1).
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		if idx >= self.len() {
+			return None;
+		}
+		let (k, offset) = Self::locate(idx);
+		let ptr = self.segments[k].load(Ordering::Acquire);
+		if ptr.is_null() {
+			return None;
+		}
+		//Safety: The index is within the segment and written elements are never moved or
+		// changed until the vector is dropped.
+		unsafe {
+			let slot = &*ptr.add(offset);
+			if slot.ready.load(Ordering::Acquire) {
+				Some(&*(*slot.value.get()).as_ptr())
+			} else {
+				None
+			}
+		}
+	}
+
+	///Returns an iterator over the written elements in order. Stops at the first element still
+	/// being written, so it always yields a prefix of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+		(0..self.len()).map_while(move |idx| self.get(idx))
+	}
+
+	///Moves every element into a regular `Vector`.
+	///
+	/// Has O(n) complexity.
+	pub fn into_vector(mut self) -> Vector<T> {
+		let len = *self.reserved.get_mut();
+		let mut vec = Vector::with_capacity(len);
+		for idx in 0..len {
+			let (k, offset) = Self::locate(idx);
+			let ptr = *self.segments[k].get_mut();
+			if ptr.is_null() {
+				continue;
+			}
+			//Safety: Pushes have finished as the vector is owned. The flag is cleared so drop
+			// won't drop the moved out element again.
+			unsafe {
+				let slot = &mut *ptr.add(offset);
+				if *slot.ready.get_mut() {
+					*slot.ready.get_mut() = false;
+					vec.push(slot.value.get_mut().as_ptr().read());
+				}
+			}
+		}
+		vec
+	}
+}
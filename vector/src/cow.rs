@@ -0,0 +1,125 @@
+use crate::*;
+use std::{ops::Deref, rc::Rc, sync::Arc};
+
+//Both flavours only differ in the reference counted pointer, so they share one definition.
+macro_rules! cow_vector {
+	($(#[$attr:meta])* $name:ident, $ptr:ident) => {
+		$(#[$attr])*
+		pub struct $name<T> {
+			pub(crate) inner: $ptr<Vector<T>>,
+		}
+
+		impl<T> Clone for $name<T> {
+			///Shares the same vector, only the reference count is increased. Has complexity O(1).
+			fn clone(&self) -> Self {
+				$name {
+					inner: $ptr::clone(&self.inner),
+				}
+			}
+		}
+
+		impl<T> Default for $name<T> {
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+
+		impl<T: Debug> Debug for $name<T> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				Debug::fmt(&*self.inner, f)
+			}
+		}
+
+		impl<T> Deref for $name<T> {
+			type Target = [T];
+
+			fn deref(&self) -> &Self::Target {
+				self.inner.as_slice()
+			}
+		}
+
+		impl<T> From<Vector<T>> for $name<T> {
+			fn from(vec: Vector<T>) -> Self {
+				$name {
+					inner: $ptr::new(vec),
+				}
+			}
+		}
+
+		impl<T: Clone> From<$name<T>> for Vector<T> {
+			fn from(vec: $name<T>) -> Self {
+				vec.into_vector()
+			}
+		}
+
+		impl<T> FromIterator<T> for $name<T> {
+			fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
+				iter.into_iter().collect::<Vector<T>>().into()
+			}
+		}
+
+		impl<T> $name<T> {
+			///Creates a new empty vector. Does not allocate the vector's buffer till it's needed.
+			pub fn new() -> Self {
+				Vector::new().into()
+			}
+
+			///Borrows the shared vector.
+			pub fn as_vector(&self) -> &Vector<T> {
+				&self.inner
+			}
+
+			///Checks if other clones share this vector.
+			pub fn is_shared(&self) -> bool {
+				$ptr::strong_count(&self.inner) > 1
+			}
+
+			///Checks if both share the same vector.
+			pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+				$ptr::ptr_eq(&this.inner, &other.inner)
+			}
+
+			///Mutably borrows the vector if no other clones share it.
+			///
+			/// Has complexity O(1).
+			pub fn get_mut(&mut self) -> Option<&mut Vector<T>> {
+				$ptr::get_mut(&mut self.inner)
+			}
+
+			///Mutably borrows the vector, first copying it if other clones share it. Clones made
+			/// before this call keep seeing the old contents.
+			///
+			/// Has O(n) complexity if the vector is shared and O(1) otherwise.
+			pub fn make_mut(&mut self) -> &mut Vector<T>
+			where
+				T: Clone,
+			{
+				$ptr::make_mut(&mut self.inner)
+			}
+
+			///Returns the vector, copying it if other clones share it.
+			///
+			/// Has O(n) complexity if the vector is shared and O(1) otherwise.
+			pub fn into_vector(self) -> Vector<T>
+			where
+				T: Clone,
+			{
+				$ptr::try_unwrap(self.inner).unwrap_or_else(|shared| (*shared).clone())
+			}
+		}
+	};
+}
+
+cow_vector!(
+	///A reference counted `Vector` with cheap clones. Mutation through `make_mut` copies the
+	/// vector only if it is shared. Use `ArcCowVector` to share between threads.
+	CowVector,
+	Rc
+);
+
+cow_vector!(
+	///An atomically reference counted `Vector` with cheap clones that can be shared between
+	/// threads. Mutation through `make_mut` copies the vector only if it is shared.
+	ArcCowVector,
+	Arc
+);
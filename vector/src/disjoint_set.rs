@@ -0,0 +1,182 @@
+use crate::*;
+
+///A union-find over the elements `0..len`, partitioning them into disjoint sets.
+///
+/// Every set is a tree of parent links whose root represents it. `find` compresses the paths it
+/// walks and `union` hangs the lower ranked tree under the other, so operations take nearly
+/// constant amortised time.
+pub struct DisjointSet {
+	pub(crate) parents: Vector<usize>,
+	pub(crate) ranks: Vector<usize>,
+	//Only meaningful at roots.
+	pub(crate) sizes: Vector<usize>,
+	pub(crate) sets: usize,
+}
+
+impl Default for DisjointSet {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Debug for DisjointSet {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("DisjointSet")
+			.field("len", &self.len())
+			.field("sets", &self.sets)
+			.finish()
+	}
+}
+
+impl Clone for DisjointSet {
+	fn clone(&self) -> Self {
+		DisjointSet {
+			parents: self.parents.clone(),
+			ranks: self.ranks.clone(),
+			sizes: self.sizes.clone(),
+			sets: self.sets,
+		}
+	}
+}
+
+impl DisjointSet {
+	///Creates an empty disjoint set. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		DisjointSet {
+			parents: Vector::new(),
+			ranks: Vector::new(),
+			sizes: Vector::new(),
+			sets: 0,
+		}
+	}
+
+	///Creates a disjoint set of `len` elements, each in a set of its own.
+	///
+	/// Has O(n) complexity.
+	pub fn with_len(len: usize) -> Self {
+		let mut set = DisjointSet {
+			parents: Vector::with_capacity(len),
+			ranks: Vector::with_capacity(len),
+			sizes: Vector::with_capacity(len),
+			sets: 0,
+		};
+		set.grow(len);
+		set
+	}
+
+	///Returns the amount of elements.
+	pub fn len(&self) -> usize {
+		self.parents.len()
+	}
+
+	///Checks if there are no elements.
+	pub fn is_empty(&self) -> bool {
+		self.parents.is_empty()
+	}
+
+	///Returns the amount of disjoint sets.
+	pub fn set_count(&self) -> usize {
+		self.sets
+	}
+
+	///Adds a new element in a set of its own and returns it.
+	///
+	/// Has complexity O(1).
+	pub fn make_set(&mut self) -> usize {
+		let elem = self.parents.len();
+		self.parents.push(elem);
+		self.ranks.push(0);
+		self.sizes.push(1);
+		self.sets += 1;
+		elem
+	}
+
+	///Adds elements in sets of their own until there are `len` of them. Does nothing if there
+	/// already are.
+	///
+	/// Has O(n) complexity in the amount of elements added.
+	pub fn grow(&mut self, len: usize) {
+		if len <= self.len() {
+			return;
+		}
+		let added = len - self.len();
+		self.parents.extend(self.len()..len);
+		self.ranks.extend(iter::repeat_n(0, added));
+		self.sizes.extend(iter::repeat_n(1, added));
+		self.sets += added;
+	}
+
+	///Returns the representative of the set containing `elem`, pointing every element on the way
+	/// directly at it.
+	///
+	/// Panics if elem is out of bounds. Has amortised complexity O(α(n)).
+	pub fn find(&mut self, elem: usize) -> usize {
+		assert!(elem < self.len(), "Index was out of bounds");
+		let mut root = elem;
+		while self.parents[root] != root {
+			root = self.parents[root];
+		}
+		let mut cur = elem;
+		while cur != root {
+			cur = mem::replace(&mut self.parents[cur], root);
+		}
+		root
+	}
+
+	///Merges the sets containing `a` and `b`.
+	///
+	/// Returns `false` if they already were the same set. Panics if either is out of bounds. Has
+	/// amortised complexity O(α(n)).
+	pub fn union(&mut self, a: usize, b: usize) -> bool {
+		let (a, b) = (self.find(a), self.find(b));
+		if a == b {
+			return false;
+		}
+		let (root, child) = if self.ranks[a] < self.ranks[b] {
+			(b, a)
+		} else {
+			(a, b)
+		};
+		self.parents[child] = root;
+		if self.ranks[a] == self.ranks[b] {
+			self.ranks[root] += 1;
+		}
+		self.sizes[root] += self.sizes[child];
+		self.sets -= 1;
+		true
+	}
+
+	///Checks if `a` and `b` are in the same set.
+	///
+	/// Panics if either is out of bounds. Has amortised complexity O(α(n)).
+	pub fn same_set(&mut self, a: usize, b: usize) -> bool {
+		self.find(a) == self.find(b)
+	}
+
+	///Returns the amount of elements in the set containing `elem`.
+	///
+	/// Panics if elem is out of bounds. Has amortised complexity O(α(n)).
+	pub fn set_size(&mut self, elem: usize) -> usize {
+		let root = self.find(elem);
+		self.sizes[root]
+	}
+
+	///Returns every set with its elements in ascending order, the sets ordered by their smallest
+	/// element.
+	///
+	/// Has O(n) complexity.
+	pub fn sets(&mut self) -> Vector<Vector<usize>> {
+		//Position of each root's set in the output, assigned the first time the root is seen.
+		let mut slots = Vector::from_elem(usize::MAX, self.len());
+		let mut sets: Vector<Vector<usize>> = Vector::with_capacity(self.sets);
+		for elem in 0..self.len() {
+			let root = self.find(elem);
+			if slots[root] == usize::MAX {
+				slots[root] = sets.len();
+				sets.push(Vector::with_capacity(self.sizes[root]));
+			}
+			sets[slots[root]].push(elem);
+		}
+		sets
+	}
+}
@@ -0,0 +1,292 @@
+use crate::*;
+
+///A resizable array of `T` with a movable gap of unused space at the cursor, making inserts
+/// and deletions close to the cursor cheap. Does not allocate upon creation.
+///
+/// The buffer is a `Vector` whose length is kept at zero, the gap buffer tracks which slots are
+/// initialised itself: `[0, gap_start)` before the cursor and `[gap_end, capacity)` after it.
+pub struct GapBuffer<T> {
+	pub(crate) buf: Vector<T>,
+	pub(crate) gap_start: usize,
+	pub(crate) gap_end: usize,
+}
+
+impl<T> Default for GapBuffer<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for GapBuffer<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T> Index<usize> for GapBuffer<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T> IndexMut<usize> for GapBuffer<T> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		self.get_mut(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T> FromIterator<T> for GapBuffer<T> {
+	fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
+		iter.into_iter().collect::<Vector<T>>().into()
+	}
+}
+
+impl<T> From<Vector<T>> for GapBuffer<T> {
+	///Reuses the vector's allocation, with the cursor at the end and the spare capacity as the gap.
+	fn from(mut vec: Vector<T>) -> Self {
+		let len = vec.len();
+		//Safety: The gap buffer takes over responsibility for the initialised elements.
+		unsafe { vec.set_len(0) };
+		GapBuffer {
+			gap_start: len,
+			gap_end: vec.capacity,
+			buf: vec,
+		}
+	}
+}
+
+impl<T> From<GapBuffer<T>> for Vector<T> {
+	///Moves the gap to the end and hands the allocation back without copying it.
+	fn from(mut buf: GapBuffer<T>) -> Self {
+		let len = buf.len();
+		buf.move_cursor(len);
+		let mut vec = mem::take(&mut buf.buf);
+		//Leave the gap buffer empty so its drop doesn't touch the moved elements.
+		buf.gap_start = 0;
+		buf.gap_end = buf.buf.capacity;
+		//Safety: The gap is at the end so every slot before `len` is initialised.
+		unsafe { vec.set_len(len) };
+		vec
+	}
+}
+
+impl<T> Drop for GapBuffer<T> {
+	fn drop(&mut self) {
+		let (front, back) = self.as_slices_mut();
+		//Safety: Both halves are initialised and never read again. The allocation itself is
+		// freed when the inner (empty) vector is dropped.
+		unsafe {
+			ptr::drop_in_place(front);
+			ptr::drop_in_place(back);
+		}
+	}
+}
+
+impl<T> GapBuffer<T> {
+	///Creates a new gap buffer. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Vector::new().into()
+	}
+
+	///Creates a new gap buffer with a preallocated gap with space for `cap` elements.
+	pub fn with_capacity(cap: usize) -> Self {
+		Vector::with_capacity(cap).into()
+	}
+
+	///Returns the amount of elements stored in the buffer.
+	pub fn len(&self) -> usize {
+		self.buf.capacity - (self.gap_end - self.gap_start)
+	}
+
+	///Checks if the buffer has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	///Returns the position of the cursor, which is the index the next inserted element gets.
+	pub fn cursor(&self) -> usize {
+		self.gap_start
+	}
+
+	///Moves the cursor (and with it the gap) to `pos`.
+	///
+	/// Panics if `pos` is greater than the length of the buffer. Has O(n) complexity in the
+	/// distance moved.
+	pub fn move_cursor(&mut self, pos: usize) {
+		assert!(pos <= self.len(), "Cursor was out of bounds");
+		if pos == self.gap_start {
+			return;
+		}
+		let ptr = self.ptr_mut();
+		if pos < self.gap_start {
+			let count = self.gap_start - pos;
+			//Safety: Moves the elements between the new and old cursor from before the gap
+			// to the end of it. Both ranges are within the allocation and may overlap.
+			unsafe { ptr::copy(ptr.add(pos), ptr.add(self.gap_end - count), count) };
+			self.gap_start -= count;
+			self.gap_end -= count;
+		} else {
+			let count = pos - self.gap_start;
+			//Safety: Moves the first elements after the gap to the start of it. Both ranges
+			// are within the allocation and may overlap.
+			unsafe { ptr::copy(ptr.add(self.gap_end), ptr.add(self.gap_start), count) };
+			self.gap_start += count;
+			self.gap_end += count;
+		}
+	}
+
+	///Inserts an element at the cursor and moves the cursor past it.
+	///
+	/// Has complexity O(1) amortised.
+	pub fn insert(&mut self, elem: T) {
+		if self.gap_start == self.gap_end {
+			self.grow();
+		}
+		//Safety: The gap is not empty and slots in the gap are uninitialised.
+		unsafe { self.ptr_mut().add(self.gap_start).write(elem) };
+		self.gap_start += 1;
+	}
+
+	///Removes the element right after the cursor, like the delete key.
+	///
+	/// Returns `None` if the cursor is at the end. Has complexity O(1).
+	pub fn delete(&mut self) -> Option<T> {
+		if self.gap_end == self.buf.capacity {
+			return None;
+		}
+		self.gap_end += 1;
+		//Safety: The slot was initialised and is now part of the gap, so it won't be read again.
+		Some(unsafe { self.ptr_mut().add(self.gap_end - 1).read() })
+	}
+
+	///Removes the element right before the cursor, like the backspace key.
+	///
+	/// Returns `None` if the cursor is at the start. Has complexity O(1).
+	pub fn backspace(&mut self) -> Option<T> {
+		if self.gap_start == 0 {
+			return None;
+		}
+		self.gap_start -= 1;
+		//Safety: The slot was initialised and is now part of the gap, so it won't be read again.
+		Some(unsafe { self.ptr_mut().add(self.gap_start).read() })
+	}
+
+	///Gets a reference to the element at index's position, not counting the gap.
+	///
+	/// Returns `None` if index is greater than the length of the buffer. Has complexity O(1).
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		let (front, back) = self.as_slices();
+		if idx < front.len() {
+			front.get(idx)
+		} else {
+			back.get(idx - front.len())
+		}
+	}
+
+	///Gets a mutable reference to the element at index's position, not counting the gap.
+	///
+	/// Returns `None` if index is greater than the length of the buffer. Has complexity O(1).
+	pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+		let (front, back) = self.as_slices_mut();
+		if idx < front.len() {
+			front.get_mut(idx)
+		} else {
+			back.get_mut(idx - front.len())
+		}
+	}
+
+	///Borrows the elements before and after the cursor as two slices.
+	///
+	/// Has complexity O(1).
+	pub fn as_slices(&self) -> (&[T], &[T]) {
+		if self.is_empty() {
+			return (&[], &[]);
+		}
+		let ptr = self
+			.buf
+			.as_ptr()
+			.expect("Non-empty gap buffer without a buffer?");
+		//Safety: Both ranges are initialised and within the allocation.
+		unsafe {
+			(
+				&*ptr::slice_from_raw_parts(ptr, self.gap_start),
+				&*ptr::slice_from_raw_parts(
+					ptr.add(self.gap_end),
+					self.buf.capacity - self.gap_end,
+				),
+			)
+		}
+	}
+
+	///Borrows the elements before and after the cursor as two mutable slices.
+	///
+	/// Has complexity O(1).
+	pub fn as_slices_mut(&mut self) -> (&mut [T], &mut [T]) {
+		if self.is_empty() {
+			return (&mut [], &mut []);
+		}
+		let ptr = self.ptr_mut();
+		//Safety: Both ranges are initialised, within the allocation and don't overlap.
+		unsafe {
+			(
+				&mut *ptr::slice_from_raw_parts_mut(ptr, self.gap_start),
+				&mut *ptr::slice_from_raw_parts_mut(
+					ptr.add(self.gap_end),
+					self.buf.capacity - self.gap_end,
+				),
+			)
+		}
+	}
+
+	///Moves the gap to the end and borrows every element as one mutable slice.
+	///
+	/// Has O(n) complexity in the distance from the cursor to the end.
+	pub fn make_contiguous(&mut self) -> &mut [T] {
+		self.move_cursor(self.len());
+		self.as_slices_mut().0
+	}
+
+	///Returns an iterator over borrowed elements of the buffer, skipping the gap.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> impl DoubleEndedIterator<Item = &T> {
+		let (front, back) = self.as_slices();
+		front.iter().chain(back.iter())
+	}
+
+	fn ptr_mut(&mut self) -> *mut T {
+		self.buf
+			.as_ptr_mut()
+			.expect("Gap buffer was used without a buffer?")
+	}
+
+	///Replaces the buffer with a larger one, keeping the gap at the cursor.
+	///
+	/// Has O(n) complexity.
+	fn grow(&mut self) {
+		let old_cap = self.buf.capacity;
+		if old_cap == usize::MAX {
+			panic!("Overflow");
+		}
+		let new_cap = ((old_cap as f64 * GROWTH_RATE).ceil() as usize).max(2);
+		let back_len = old_cap - self.gap_end;
+		let mut new = Vector::<T>::with_capacity(new_cap);
+		let new_ptr = new.as_ptr_mut().expect("Allocation failed?");
+		if let Some(old_ptr) = self.buf.as_ptr() {
+			//Safety: The new allocation is separate from the old one, both halves keep their
+			// distance to the start and end of the buffer respectively.
+			unsafe {
+				ptr::copy_nonoverlapping(old_ptr, new_ptr, self.gap_start);
+				ptr::copy_nonoverlapping(
+					old_ptr.add(self.gap_end),
+					new_ptr.add(new_cap - back_len),
+					back_len,
+				);
+			}
+		}
+		//The old vector has a length of zero, so dropping it only frees the allocation.
+		self.buf = new;
+		self.gap_end = new_cap - back_len;
+	}
+}
@@ -0,0 +1,222 @@
+use crate::*;
+use std::marker::PhantomData;
+
+///A typed index into an `IndexVector`. Usually declared with `index_newtype!`.
+pub trait Idx: Copy + 'static {
+	///Creates an index from a position. Panics if the position doesn't fit the index type.
+	fn new(idx: usize) -> Self;
+	///Returns the position this index points to.
+	fn index(self) -> usize;
+}
+
+impl Idx for usize {
+	fn new(idx: usize) -> Self {
+		idx
+	}
+
+	fn index(self) -> usize {
+		self
+	}
+}
+
+///Declares index newtypes over `u32` implementing `Idx`, to keep indices into different
+/// `IndexVector`s apart.
+///
+/// ```
+/// vector::index_newtype! {
+///     pub struct NodeId;
+///     pub struct EdgeId;
+/// }
+///
+/// let mut nodes = vector::IndexVector::<NodeId, &str>::new();
+/// let a = nodes.push("a");
+/// let b = nodes.push("b");
+/// assert_eq!(nodes[b], "b");
+/// assert!(a < b);
+/// ```
+///
+/// The generated types are `Copy`, ordered, hashable and print as `Name(n)`.
+#[macro_export]
+macro_rules! index_newtype {
+	($($(#[$attr:meta])* $vis:vis struct $name:ident;)+) => {
+		$(
+			$(#[$attr])*
+			#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+			$vis struct $name(u32);
+
+			impl $crate::Idx for $name {
+				fn new(idx: usize) -> Self {
+					assert!(idx <= u32::MAX as usize, "Index overflowed {}", stringify!($name));
+					$name(idx as u32)
+				}
+
+				fn index(self) -> usize {
+					self.0 as usize
+				}
+			}
+		)+
+	};
+}
+
+///A `Vector` that can only be indexed by `I`, so indices meant for another vector don't
+/// type check.
+pub struct IndexVector<I: Idx, T> {
+	pub(crate) vec: Vector<T>,
+	pub(crate) _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> Default for IndexVector<I, T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<I: Idx, T: Debug> Debug for IndexVector<I, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Debug::fmt(&self.vec, f)
+	}
+}
+
+impl<I: Idx, T: Clone> Clone for IndexVector<I, T> {
+	fn clone(&self) -> Self {
+		self.vec.clone().into()
+	}
+}
+
+impl<I: Idx, T> Index<I> for IndexVector<I, T> {
+	type Output = T;
+	fn index(&self, index: I) -> &Self::Output {
+		&self.vec[index.index()]
+	}
+}
+
+impl<I: Idx, T> IndexMut<I> for IndexVector<I, T> {
+	fn index_mut(&mut self, index: I) -> &mut Self::Output {
+		&mut self.vec[index.index()]
+	}
+}
+
+impl<I: Idx, T> From<Vector<T>> for IndexVector<I, T> {
+	fn from(vec: Vector<T>) -> Self {
+		IndexVector {
+			vec,
+			_marker: PhantomData,
+		}
+	}
+}
+
+impl<I: Idx, T> From<IndexVector<I, T>> for Vector<T> {
+	fn from(vec: IndexVector<I, T>) -> Self {
+		vec.vec
+	}
+}
+
+impl<I: Idx, T> FromIterator<T> for IndexVector<I, T> {
+	fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
+		iter.into_iter().collect::<Vector<T>>().into()
+	}
+}
+
+impl<I: Idx, T> IndexVector<I, T> {
+	///Creates a new empty vector. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Vector::new().into()
+	}
+
+	///Creates a new vector with a preallocated buffer with space for `cap` elements.
+	pub fn with_capacity(cap: usize) -> Self {
+		Vector::with_capacity(cap).into()
+	}
+
+	///Checks if the vector has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.vec.is_empty()
+	}
+
+	///Returns the amount of elements stored in the vector.
+	pub fn len(&self) -> usize {
+		self.vec.len()
+	}
+
+	///Returns the index the next pushed element will get.
+	pub fn next_index(&self) -> I {
+		I::new(self.vec.len())
+	}
+
+	///Inserts an element at the back of the vector and returns its index.
+	///
+	/// Panics if the index doesn't fit in `I`. Has complexity O(1).
+	pub fn push(&mut self, elem: T) -> I {
+		let idx = self.next_index();
+		self.vec.push(elem);
+		idx
+	}
+
+	///Removes the last element in the vector.
+	///
+	/// Returns `None` if the vector is empty. Has O(1) complexity.
+	pub fn pop(&mut self) -> Option<T> {
+		self.vec.pop()
+	}
+
+	///Gets a reference to the element at index's position.
+	///
+	/// Returns `None` if index is out of bounds. Has complexity O(1).
+	pub fn get(&self, idx: I) -> Option<&T> {
+		self.vec.get(idx.index())
+	}
+
+	///Gets a mutable reference to the element at index's position.
+	///
+	/// Returns `None` if index is out of bounds. Has complexity O(1).
+	pub fn get_mut(&mut self, idx: I) -> Option<&mut T> {
+		self.vec.get_mut(idx.index())
+	}
+
+	///Borrows the wrapped vector.
+	pub fn as_vector(&self) -> &Vector<T> {
+		&self.vec
+	}
+
+	///Returns the wrapped vector.
+	pub fn into_vector(self) -> Vector<T> {
+		self.vec
+	}
+
+	///Returns an iterator over borrowed elements of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> std::slice::Iter<'_, T> {
+		self.vec.as_slice().iter()
+	}
+
+	///Returns an iterator over mutably borrowed elements of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+		self.vec.as_slice_mut().iter_mut()
+	}
+
+	///Returns an iterator over every index in the vector.
+	///
+	/// Has complexity O(1).
+	pub fn indices(&self) -> impl DoubleEndedIterator<Item = I> {
+		(0..self.vec.len()).map(I::new)
+	}
+
+	///Returns an iterator over borrowed elements together with their index.
+	///
+	/// Has complexity O(1).
+	pub fn iter_enumerated(&self) -> impl DoubleEndedIterator<Item = (I, &T)> {
+		self.iter().enumerate().map(|(i, elem)| (I::new(i), elem))
+	}
+
+	///Returns an iterator over mutably borrowed elements together with their index.
+	///
+	/// Has complexity O(1).
+	pub fn iter_enumerated_mut(&mut self) -> impl DoubleEndedIterator<Item = (I, &mut T)> {
+		self.iter_mut()
+			.enumerate()
+			.map(|(i, elem)| (I::new(i), elem))
+	}
+}
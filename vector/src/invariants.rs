@@ -0,0 +1,127 @@
+use crate::*;
+
+///Written right before and after every buffer with the `debug-invariants` feature, so writes
+/// out of bounds are caught when the buffer is checked or freed.
+#[cfg(feature = "debug-invariants")]
+const CANARY: u64 = 0x5AFE_C0DE_CA4A_4127;
+#[cfg(feature = "debug-invariants")]
+const CANARY_LEN: usize = mem::size_of::<u64>();
+
+impl<T, const ALIGN: usize> Vector<T, ALIGN> {
+	//Bytes in front of the elements, fitting the front canary while keeping the elements aligned.
+	#[cfg(feature = "debug-invariants")]
+	fn canary_offset() -> usize {
+		Self::layout(0).align().max(CANARY_LEN)
+	}
+
+	///Returns the layout of the whole allocation backing a buffer of `cap` elements, including
+	/// the canaries if enabled.
+	#[cfg(feature = "debug-invariants")]
+	fn allocation_layout(cap: usize) -> Layout {
+		let data = Self::layout(cap);
+		let size = data
+			.size()
+			.checked_add(Self::canary_offset() + CANARY_LEN)
+			.expect("Overflow");
+		Layout::from_size_align(size, data.align().max(CANARY_LEN)).expect("Overflow")
+	}
+
+	#[cfg(not(feature = "debug-invariants"))]
+	fn allocation_layout(cap: usize) -> Layout {
+		Self::layout(cap)
+	}
+
+	///Allocates a buffer for `cap` elements.
+	///
+	/// Panics if the allocation fails. `T` must not be zero sized.
+	pub(crate) fn allocate(cap: usize) -> NonNull<T> {
+		let layout = Self::allocation_layout(cap);
+		//Safety: Layout is type and capacity checked.
+		let ptr = unsafe { alloc::alloc(layout) };
+		assert!(!ptr.is_null());
+		#[cfg(feature = "debug-invariants")]
+		//Safety: The front canary is within the offset and aligned to it, the back one follows
+		// the elements and may be unaligned.
+		let ptr = unsafe {
+			let data = ptr.add(Self::canary_offset());
+			(data.sub(CANARY_LEN) as *mut u64).write(CANARY);
+			(data.add(Self::layout(cap).size()) as *mut u64).write_unaligned(CANARY);
+			data
+		};
+		NonNull::new(ptr as *mut T).expect("Allocation was null despite being checked?")
+	}
+
+	///Frees a buffer returned by `allocate`, checking its canaries first if enabled.
+	///
+	/// # Safety
+	/// `ptr` must come from `allocate(cap)` and not be used afterwards.
+	pub(crate) unsafe fn deallocate(ptr: NonNull<T>, cap: usize) {
+		#[cfg(feature = "debug-invariants")]
+		Self::check_canaries(ptr, cap);
+		let start = ptr.as_ptr() as *mut u8;
+		#[cfg(feature = "debug-invariants")]
+		let start = start.sub(Self::canary_offset());
+		alloc::dealloc(start, Self::allocation_layout(cap));
+	}
+
+	#[cfg(feature = "debug-invariants")]
+	fn check_canaries(ptr: NonNull<T>, cap: usize) {
+		let data = ptr.as_ptr() as *const u8;
+		//Safety: Both canaries were written by `allocate` for this capacity.
+		let (front, back) = unsafe {
+			(
+				(data.sub(CANARY_LEN) as *const u64).read(),
+				(data.add(Self::layout(cap).size()) as *const u64).read_unaligned(),
+			)
+		};
+		assert_eq!(
+			front, CANARY,
+			"Write before the start of the vector's buffer"
+		);
+		assert_eq!(back, CANARY, "Write past the end of the vector's buffer");
+	}
+
+	///Checks that the length fits the capacity, that the capacity matches the allocation and
+	/// that nothing was written just outside the buffer.
+	///
+	/// Panics describing the first broken invariant. Called after every mutating method when the
+	/// `debug-invariants` feature is enabled. Has complexity O(1).
+	#[cfg(feature = "debug-invariants")]
+	pub fn check_invariants(&self) {
+		assert!(
+			self.size <= self.capacity,
+			"Length {} exceeds capacity {}",
+			self.size,
+			self.capacity
+		);
+		if mem::size_of::<T>() == 0 {
+			assert!(self.data.is_none(), "Zero sized elements with a buffer");
+			assert_eq!(
+				self.capacity,
+				usize::MAX,
+				"Zero sized elements with a limited capacity"
+			);
+			return;
+		}
+		match self.data {
+			None => assert_eq!(self.capacity, 0, "Capacity without a buffer"),
+			Some(ptr) => {
+				assert_eq!(
+					ptr.as_ptr() as usize % Self::layout(0).align(),
+					0,
+					"Buffer is misaligned"
+				);
+				//The back canary is found through the capacity, so a capacity not matching the
+				// allocation reads garbage instead.
+				Self::check_canaries(ptr, self.capacity);
+			}
+		}
+	}
+
+	///Runs `check_invariants` if the `debug-invariants` feature is enabled.
+	#[inline]
+	pub(crate) fn debug_check(&self) {
+		#[cfg(feature = "debug-invariants")]
+		self.check_invariants();
+	}
+}
@@ -0,0 +1,121 @@
+use crate::*;
+use std::io::{self, BufRead, Read, Seek, SeekFrom, Write};
+
+///Appends everything written to the back of the vector. Never fails or writes partially.
+impl<const ALIGN: usize> Write for Vector<u8, ALIGN> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.extend_from_slice(buf);
+		Ok(buf.len())
+	}
+
+	fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+		self.extend_from_slice(buf);
+		Ok(())
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		Ok(())
+	}
+}
+
+///Wraps a `Vector<u8>` with a read position to read from it like a file.
+#[derive(Debug, Default)]
+pub struct VectorCursor {
+	pub(crate) vec: Vector<u8>,
+	pub(crate) pos: u64,
+}
+
+impl VectorCursor {
+	///Creates a new cursor at the start of `vec`.
+	pub fn new(vec: Vector<u8>) -> Self {
+		VectorCursor { vec, pos: 0 }
+	}
+
+	///Returns the wrapped vector.
+	pub fn into_inner(self) -> Vector<u8> {
+		self.vec
+	}
+
+	///Borrows the wrapped vector.
+	pub fn get_ref(&self) -> &Vector<u8> {
+		&self.vec
+	}
+
+	///Mutably borrows the wrapped vector. The position is left as is even if the vector shrinks.
+	pub fn get_mut(&mut self) -> &mut Vector<u8> {
+		&mut self.vec
+	}
+
+	///Returns the current read position in bytes from the start.
+	pub fn position(&self) -> u64 {
+		self.pos
+	}
+
+	///Sets the read position. Positions past the end are allowed and read nothing.
+	pub fn set_position(&mut self, pos: u64) {
+		self.pos = pos;
+	}
+
+	///Borrows everything from the read position to the end.
+	fn remaining(&self) -> &[u8] {
+		let slice = self.vec.as_slice();
+		let start = (self.pos.min(slice.len() as u64)) as usize;
+		&slice[start..]
+	}
+}
+
+impl Read for VectorCursor {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		let remaining = self.remaining();
+		let len = remaining.len().min(buf.len());
+		buf[..len].copy_from_slice(&remaining[..len]);
+		self.pos += len as u64;
+		Ok(len)
+	}
+
+	fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+		let remaining = self.remaining();
+		if remaining.len() < buf.len() {
+			return Err(io::Error::new(
+				io::ErrorKind::UnexpectedEof,
+				"Cursor reached the end of the vector",
+			));
+		}
+		buf.copy_from_slice(&remaining[..buf.len()]);
+		self.pos += buf.len() as u64;
+		Ok(())
+	}
+}
+
+impl BufRead for VectorCursor {
+	fn fill_buf(&mut self) -> io::Result<&[u8]> {
+		Ok(self.remaining())
+	}
+
+	fn consume(&mut self, amt: usize) {
+		self.pos += amt as u64;
+	}
+}
+
+impl Seek for VectorCursor {
+	fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+		let (base, offset) = match pos {
+			SeekFrom::Start(pos) => {
+				self.pos = pos;
+				return Ok(pos);
+			}
+			SeekFrom::End(offset) => (self.vec.len() as u64, offset),
+			SeekFrom::Current(offset) => (self.pos, offset),
+		};
+		match base.checked_add_signed(offset) {
+			Some(pos) => {
+				self.pos = pos;
+				Ok(pos)
+			}
+			None => Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"Seek to a negative or overflowing position",
+			)),
+		}
+	}
+}
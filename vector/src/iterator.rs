@@ -1,13 +1,13 @@
 use crate::*;
 
-pub struct VectorIterator<T> {
+pub struct VectorIterator<T, const ALIGN: usize = 0> {
 	pub(crate) data: Option<NonNull<T>>,
 	pub(crate) capacity: usize,
 	pub(crate) index: usize,
 	pub(crate) index_back: usize,
 }
 
-impl<T> Iterator for VectorIterator<T> {
+impl<T, const ALIGN: usize> Iterator for VectorIterator<T, ALIGN> {
 	type Item = T;
 	fn next(&mut self) -> Option<Self::Item> {
 		self.index = self.index.wrapping_add(1);
@@ -16,7 +16,7 @@ impl<T> Iterator for VectorIterator<T> {
 			return None;
 		}
 		let ptr = if mem::size_of::<T>() == 0 {
-			self as *mut VectorIterator<T> as *mut T
+			self as *mut VectorIterator<T, ALIGN> as *mut T
 		} else {
 			self.data?.as_ptr()
 		};
@@ -26,7 +26,7 @@ impl<T> Iterator for VectorIterator<T> {
 	}
 }
 
-impl<T> DoubleEndedIterator for VectorIterator<T> {
+impl<T, const ALIGN: usize> DoubleEndedIterator for VectorIterator<T, ALIGN> {
 	fn next_back(&mut self) -> Option<Self::Item> {
 		self.index_back = self.index_back.wrapping_sub(1);
 		if self.index_back == usize::MAX || self.index_back == self.index {
@@ -34,7 +34,7 @@ impl<T> DoubleEndedIterator for VectorIterator<T> {
 			return None;
 		}
 		let ptr = if mem::size_of::<T>() == 0 {
-			self as *mut VectorIterator<T> as *mut T
+			self as *mut VectorIterator<T, ALIGN> as *mut T
 		} else {
 			self.data?.as_ptr()
 		};
@@ -46,30 +46,42 @@ impl<T> DoubleEndedIterator for VectorIterator<T> {
 
 //This is needed due to the pointers being moved into the iterator struct. This means that the vector
 // is never droppped itself.
-impl<T> Drop for VectorIterator<T> {
+impl<T, const ALIGN: usize> Drop for VectorIterator<T, ALIGN> {
 	fn drop(&mut self) {
-		let capacity = self.capacity;
-		let data = self.data;
-		//Do proper drops for remaining items in the iterator
-		for _ in self {}
-		//Transform the iterator back into a vector to let vector::drop handle resource freeing
-		let _ = Vector {
-			data,
-			capacity,
-			size: 0,
+		//Frees the buffer after the remaining items are dropped, even if one of them panics.
+		let _guard = DeallocGuard::<T, ALIGN> {
+			data: self.data,
+			capacity: self.capacity,
 		};
+		let start = self.index.wrapping_add(1);
+		if start >= self.index_back {
+			return;
+		}
+		let ptr = if mem::size_of::<T>() == 0 {
+			self as *mut VectorIterator<T, ALIGN> as *mut T
+		} else {
+			match self.data {
+				Some(ptr) => ptr.as_ptr(),
+				None => return,
+			}
+		};
+		let remaining = self.index_back - start;
+		self.index = self.index_back.wrapping_sub(1);
+		//Safety: The items between both ends haven't been read yet. Dropping them as a slice
+		// keeps dropping the rest if one panics.
+		unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr.add(start), remaining)) };
 	}
 }
 
 //It's probably a better idea from a design perspective to use .iter() and .iter_mut() from std slice
 // But that goes against the design principle of rebuilding the standard library containers myself.
-pub struct BorrowedVectorIterator<'a, T> {
-	pub(crate) vector: &'a Vector<T>,
+pub struct BorrowedVectorIterator<'a, T, const ALIGN: usize = 0> {
+	pub(crate) vector: &'a Vector<T, ALIGN>,
 	pub(crate) index: usize,
 	pub(crate) index_back: usize,
 }
 
-impl<'a, T> Iterator for BorrowedVectorIterator<'a, T> {
+impl<'a, T, const ALIGN: usize> Iterator for BorrowedVectorIterator<'a, T, ALIGN> {
 	type Item = &'a T;
 	fn next(&mut self) -> Option<Self::Item> {
 		let next = self.index.wrapping_add(1);
@@ -81,7 +93,7 @@ impl<'a, T> Iterator for BorrowedVectorIterator<'a, T> {
 	}
 }
 
-impl<'a, T> DoubleEndedIterator for BorrowedVectorIterator<'a, T> {
+impl<'a, T, const ALIGN: usize> DoubleEndedIterator for BorrowedVectorIterator<'a, T, ALIGN> {
 	fn next_back(&mut self) -> Option<Self::Item> {
 		let next = self.index_back.wrapping_sub(1);
 		if next == self.index {
@@ -92,13 +104,13 @@ impl<'a, T> DoubleEndedIterator for BorrowedVectorIterator<'a, T> {
 	}
 }
 
-pub struct BorrowedVectorIteratorMut<'a, T> {
-	pub(crate) vector: &'a mut Vector<T>,
+pub struct BorrowedVectorIteratorMut<'a, T, const ALIGN: usize = 0> {
+	pub(crate) vector: &'a mut Vector<T, ALIGN>,
 	pub(crate) index: usize,
 	pub(crate) index_back: usize,
 }
 
-impl<'a, T> Iterator for BorrowedVectorIteratorMut<'a, T> {
+impl<'a, T, const ALIGN: usize> Iterator for BorrowedVectorIteratorMut<'a, T, ALIGN> {
 	type Item = &'a mut T;
 	fn next(&mut self) -> Option<Self::Item> {
 		let next = self.index.wrapping_add(1);
@@ -113,12 +125,11 @@ impl<'a, T> Iterator for BorrowedVectorIteratorMut<'a, T> {
 		// you can get mutable references to several elements at once)
 		self.vector
 			.get_mut(self.index)
-			.map(|r| unsafe { (r as *mut T).as_mut() })
-			.flatten()
+			.and_then(|r| unsafe { (r as *mut T).as_mut() })
 	}
 }
 
-impl<'a, T> DoubleEndedIterator for BorrowedVectorIteratorMut<'a, T> {
+impl<'a, T, const ALIGN: usize> DoubleEndedIterator for BorrowedVectorIteratorMut<'a, T, ALIGN> {
 	fn next_back(&mut self) -> Option<Self::Item> {
 		let next = self.index_back.wrapping_sub(1);
 		if next == self.index {
@@ -132,7 +143,6 @@ impl<'a, T> DoubleEndedIterator for BorrowedVectorIteratorMut<'a, T> {
 		// you can get mutable references to several elements at once)
 		self.vector
 			.get_mut(self.index_back)
-			.map(|r| unsafe { (r as *mut T).as_mut() })
-			.flatten()
+			.and_then(|r| unsafe { (r as *mut T).as_mut() })
 	}
 }
@@ -0,0 +1,285 @@
+use crate::*;
+
+///A vector of rows of varying length stored in compressed row (CSR) layout: every item lives in
+/// one `Vector<T>` and a second vector holds where each row ends. Needs two allocations in
+/// total instead of one per row.
+pub struct JaggedVector<T> {
+	pub(crate) items: Vector<T>,
+	//End of each row in `items`, the start is the previous row's end.
+	pub(crate) ends: Vector<usize>,
+}
+
+impl<T> Default for JaggedVector<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for JaggedVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T: Clone> Clone for JaggedVector<T> {
+	fn clone(&self) -> Self {
+		JaggedVector {
+			items: self.items.clone(),
+			ends: self.ends.clone(),
+		}
+	}
+}
+
+impl<T> Index<usize> for JaggedVector<T> {
+	type Output = [T];
+	fn index(&self, index: usize) -> &Self::Output {
+		self.row(index)
+	}
+}
+
+impl<T> IndexMut<usize> for JaggedVector<T> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		self.row_mut(index)
+	}
+}
+
+impl<T> From<Vector<Vector<T>>> for JaggedVector<T> {
+	///Moves the items of every row into one buffer. Has O(n) complexity in the amount of items.
+	fn from(rows: Vector<Vector<T>>) -> Self {
+		let total = rows.iter().map(Vector::len).sum();
+		let mut vec = JaggedVector::with_capacity(rows.len(), total);
+		for row in rows {
+			vec.push_row(row);
+		}
+		vec
+	}
+}
+
+impl<T: Clone> From<&JaggedVector<T>> for Vector<Vector<T>> {
+	fn from(vec: &JaggedVector<T>) -> Self {
+		vec.iter()
+			.map(|row| row.iter().cloned().collect())
+			.collect()
+	}
+}
+
+impl<T, R: IntoIterator<Item = T>> FromIterator<R> for JaggedVector<T> {
+	fn from_iter<A: IntoIterator<Item = R>>(iter: A) -> Self {
+		let mut vec = JaggedVector::new();
+		for row in iter {
+			vec.push_row(row);
+		}
+		vec
+	}
+}
+
+impl<T> JaggedVector<T> {
+	///Creates a new vector without rows. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		JaggedVector {
+			items: Vector::new(),
+			ends: Vector::new(),
+		}
+	}
+
+	///Creates a new vector with space preallocated for `rows` rows holding `items` items in total.
+	pub fn with_capacity(rows: usize, items: usize) -> Self {
+		JaggedVector {
+			items: Vector::with_capacity(items),
+			ends: Vector::with_capacity(rows),
+		}
+	}
+
+	///Checks if the vector has no rows.
+	pub fn is_empty(&self) -> bool {
+		self.ends.is_empty()
+	}
+
+	///Returns the amount of rows.
+	pub fn len(&self) -> usize {
+		self.ends.len()
+	}
+
+	///Returns the amount of items across every row.
+	pub fn items_len(&self) -> usize {
+		self.items.len()
+	}
+
+	fn bounds(&self, idx: usize) -> Option<(usize, usize)> {
+		let end = *self.ends.get(idx)?;
+		let start = if idx == 0 { 0 } else { self.ends[idx - 1] };
+		Some((start, end))
+	}
+
+	///Inserts a row at the back with the items of `row` and returns its index.
+	///
+	/// Has O(n) complexity in the length of the row.
+	pub fn push_row<R: IntoIterator<Item = T>>(&mut self, row: R) -> usize {
+		//Items of the row are only kept once it's complete, so a panicking iterator doesn't leave
+		// them behind in no row.
+		let mut guard = RowGuard {
+			start: self.items.len(),
+			items: &mut self.items,
+		};
+		for item in row {
+			guard.items.push(item);
+		}
+		guard.start = guard.items.len();
+		drop(guard);
+		self.ends.push(self.items.len());
+		self.ends.len() - 1
+	}
+
+	///Inserts an item at the back of the last row.
+	///
+	/// Panics if there are no rows. Has complexity O(1).
+	pub fn push_to_last_row(&mut self, item: T) {
+		let last = self.ends.len().checked_sub(1).expect("No rows to push to");
+		self.items.push(item);
+		self.ends[last] += 1;
+	}
+
+	///Inserts every item of `items` at the back of the last row.
+	///
+	/// Panics if there are no rows. Has O(n) complexity in the amount of items.
+	pub fn extend_last_row<R: IntoIterator<Item = T>>(&mut self, items: R) {
+		for item in items {
+			self.push_to_last_row(item);
+		}
+	}
+
+	///Removes the last row, returning its items.
+	///
+	/// Returns `None` if there are no rows. Has O(n) complexity in the length of the row.
+	pub fn pop_row(&mut self) -> Option<Vector<T>> {
+		self.ends.pop()?;
+		let start = self.ends.as_slice().last().copied().unwrap_or(0);
+		let mut row = Vector::with_capacity(self.items.len() - start);
+		while self.items.len() > start {
+			row.push(self.items.pop().expect("Length was checked?"));
+		}
+		row.as_slice_mut().reverse();
+		Some(row)
+	}
+
+	///Borrows the row at `idx`.
+	///
+	/// Returns `None` if idx is out of bounds. Has complexity O(1).
+	pub fn get(&self, idx: usize) -> Option<&[T]> {
+		let (start, end) = self.bounds(idx)?;
+		Some(&self.items.as_slice()[start..end])
+	}
+
+	///Mutably borrows the row at `idx`.
+	///
+	/// Returns `None` if idx is out of bounds. Has complexity O(1).
+	pub fn get_mut(&mut self, idx: usize) -> Option<&mut [T]> {
+		let (start, end) = self.bounds(idx)?;
+		Some(&mut self.items.as_slice_mut()[start..end])
+	}
+
+	///Borrows the row at `idx`.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(1).
+	pub fn row(&self, idx: usize) -> &[T] {
+		self.get(idx).expect("Index was out of bounds")
+	}
+
+	///Mutably borrows the row at `idx`.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(1).
+	pub fn row_mut(&mut self, idx: usize) -> &mut [T] {
+		self.get_mut(idx).expect("Index was out of bounds")
+	}
+
+	///Borrows the items of every row, one row after the other.
+	///
+	/// Has complexity O(1).
+	pub fn items(&self) -> &[T] {
+		self.items.as_slice()
+	}
+
+	///Mutably borrows the items of every row, one row after the other.
+	///
+	/// Has complexity O(1).
+	pub fn items_mut(&mut self) -> &mut [T] {
+		self.items.as_slice_mut()
+	}
+
+	///Removes every row.
+	///
+	/// Has O(n) complexity in the amount of items.
+	pub fn clear(&mut self) {
+		self.items.clear();
+		self.ends.clear();
+	}
+
+	///Returns an iterator over the rows as slices.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> Rows<'_, T> {
+		Rows {
+			vec: self,
+			front: 0,
+			back: self.len(),
+		}
+	}
+}
+
+///Iterator over the rows of a `JaggedVector`.
+pub struct Rows<'a, T> {
+	pub(crate) vec: &'a JaggedVector<T>,
+	pub(crate) front: usize,
+	pub(crate) back: usize,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+	type Item = &'a [T];
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.front += 1;
+		self.vec.get(self.front - 1)
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for Rows<'a, T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		self.vec.get(self.back)
+	}
+}
+
+impl<'a, T> ExactSizeIterator for Rows<'a, T> {}
+
+impl<'a, T> IntoIterator for &'a JaggedVector<T> {
+	type Item = &'a [T];
+	type IntoIter = Rows<'a, T>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+///Drops the items pushed after `start` when `push_row` unwinds.
+struct RowGuard<'a, T> {
+	items: &'a mut Vector<T>,
+	start: usize,
+}
+
+impl<'a, T> Drop for RowGuard<'a, T> {
+	fn drop(&mut self) {
+		while self.items.len() > self.start {
+			self.items.pop();
+		}
+	}
+}
@@ -0,0 +1,278 @@
+use crate::*;
+use std::ops::Deref;
+
+///A single change to apply to the vector. Applying one returns the edit that reverts it.
+#[derive(Debug)]
+pub(crate) enum Edit<T> {
+	Push(T),
+	Pop,
+	Insert(usize, T),
+	Remove(usize),
+	Set(usize, T),
+}
+
+impl<T> Edit<T> {
+	fn apply(self, vec: &mut Vector<T>) -> Edit<T> {
+		match self {
+			Edit::Push(elem) => {
+				vec.push(elem);
+				Edit::Pop
+			}
+			Edit::Pop => Edit::Push(vec.pop().expect("Journal out of sync with vector")),
+			Edit::Insert(idx, elem) => {
+				vec.insert(idx, elem);
+				Edit::Remove(idx)
+			}
+			Edit::Remove(idx) => Edit::Insert(idx, vec.remove(idx)),
+			Edit::Set(idx, elem) => Edit::Set(idx, mem::replace(&mut vec[idx], elem)),
+		}
+	}
+}
+
+//Applies a group of edits newest first and returns the edits reverting them, in the order they
+// were applied. Applying that group the same way restores the original.
+fn apply_group<T>(vec: &mut Vector<T>, mut group: Vector<Edit<T>>) -> Vector<Edit<T>> {
+	let mut inverse = Vector::with_capacity(group.len());
+	while let Some(edit) = group.pop() {
+		inverse.push(edit.apply(vec));
+	}
+	inverse
+}
+
+///Wraps a `Vector` and journals every change as the edit that reverts it, so changes can be
+/// undone and redone in steps marked by `checkpoint` without keeping copies of the vector.
+///
+/// Reading goes through `Deref` to a slice.
+pub struct JournaledVector<T> {
+	pub(crate) vec: Vector<T>,
+	//Edits reverting the changes since the last checkpoint.
+	pub(crate) pending: Vector<Edit<T>>,
+	pub(crate) undo: History<T>,
+	pub(crate) redo: Vector<Vector<Edit<T>>>,
+}
+
+///A stack of checkpoints keeping only the newest `limit`, stored as a ring so dropping the oldest
+/// one is O(1).
+pub(crate) struct History<T> {
+	//Slots are only added until there are `limit` of them, after which the oldest is overwritten.
+	pub(crate) slots: Vector<Vector<Edit<T>>>,
+	//Physical index of the oldest checkpoint.
+	pub(crate) head: usize,
+	pub(crate) len: usize,
+	pub(crate) limit: usize,
+}
+
+impl<T> History<T> {
+	fn new(limit: usize) -> Self {
+		assert!(limit > 0, "History limit must be at least 1");
+		History {
+			slots: Vector::new(),
+			head: 0,
+			len: 0,
+			limit,
+		}
+	}
+
+	fn push(&mut self, group: Vector<Edit<T>>) {
+		if self.len < self.slots.len() {
+			let slot = (self.head + self.len) % self.slots.len();
+			self.slots[slot] = group;
+			self.len += 1;
+		} else if self.slots.len() < self.limit {
+			//The ring only wraps once it's full, so the newest slot is the last one.
+			self.slots.push(group);
+			self.len += 1;
+		} else {
+			self.slots[self.head] = group;
+			self.head = (self.head + 1) % self.slots.len();
+		}
+	}
+
+	fn pop(&mut self) -> Option<Vector<Edit<T>>> {
+		if self.len == 0 {
+			return None;
+		}
+		self.len -= 1;
+		let slot = (self.head + self.len) % self.slots.len();
+		Some(mem::take(&mut self.slots[slot]))
+	}
+
+	fn clear(&mut self) {
+		self.slots.clear();
+		self.head = 0;
+		self.len = 0;
+	}
+}
+
+impl<T> Default for JournaledVector<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for JournaledVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Debug::fmt(&self.vec, f)
+	}
+}
+
+impl<T> Deref for JournaledVector<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &Self::Target {
+		self.vec.as_slice()
+	}
+}
+
+impl<T> From<Vector<T>> for JournaledVector<T> {
+	fn from(vec: Vector<T>) -> Self {
+		JournaledVector {
+			vec,
+			pending: Vector::new(),
+			undo: History::new(usize::MAX),
+			redo: Vector::new(),
+		}
+	}
+}
+
+impl<T> JournaledVector<T> {
+	///Creates a new empty vector with unbounded history. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Vector::new().into()
+	}
+
+	///Creates a new empty vector which only keeps the last `limit` checkpoints, dropping older
+	/// ones as new checkpoints are made.
+	///
+	/// Panics if limit is 0, as every change would be dropped.
+	pub fn with_history_limit(limit: usize) -> Self {
+		let mut vec = Self::new();
+		vec.undo = History::new(limit);
+		vec
+	}
+
+	///Borrows the wrapped vector.
+	pub fn as_vector(&self) -> &Vector<T> {
+		&self.vec
+	}
+
+	///Returns the wrapped vector, dropping the history.
+	pub fn into_vector(self) -> Vector<T> {
+		self.vec
+	}
+
+	///Returns the amount of checkpoints that can be undone, counting changes made since the
+	/// last checkpoint as one.
+	pub fn undo_len(&self) -> usize {
+		self.undo.len + !self.pending.is_empty() as usize
+	}
+
+	///Returns the amount of undone checkpoints that can be redone.
+	pub fn redo_len(&self) -> usize {
+		self.redo.len()
+	}
+
+	///Ends the current step, so the next `undo` reverts every change made since the previous
+	/// checkpoint. Does nothing if nothing changed.
+	///
+	/// Has complexity O(1), plus dropping the oldest checkpoint if the history is full.
+	pub fn checkpoint(&mut self) {
+		if self.pending.is_empty() {
+			return;
+		}
+		self.undo.push(mem::take(&mut self.pending));
+	}
+
+	///Reverts every change back to the previous checkpoint, including changes not yet
+	/// checkpointed. Returns false if there was nothing to undo.
+	///
+	/// Has O(n) complexity in the amount of reverted changes.
+	pub fn undo(&mut self) -> bool {
+		self.checkpoint();
+		match self.undo.pop() {
+			Some(group) => {
+				let redo = apply_group(&mut self.vec, group);
+				self.redo.push(redo);
+				true
+			}
+			None => false,
+		}
+	}
+
+	///Reapplies the last undone checkpoint. Returns false if there was nothing to redo, which
+	/// is also the case after any change made since the last undo.
+	///
+	/// Has O(n) complexity in the amount of reapplied changes.
+	pub fn redo(&mut self) -> bool {
+		match self.redo.pop() {
+			Some(group) => {
+				let undo = apply_group(&mut self.vec, group);
+				self.undo.push(undo);
+				true
+			}
+			None => false,
+		}
+	}
+
+	///Drops every checkpoint and pending change, keeping the current contents.
+	pub fn clear_history(&mut self) {
+		self.pending.clear();
+		self.undo.clear();
+		self.redo.clear();
+	}
+
+	fn record(&mut self, inverse: Edit<T>) {
+		self.pending.push(inverse);
+		self.redo.clear();
+	}
+
+	///Inserts an element at the back of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn push(&mut self, elem: T) {
+		let inverse = Edit::Push(elem).apply(&mut self.vec);
+		self.record(inverse);
+	}
+
+	///Inserts an element at `idx`.
+	///
+	/// Panics if idx is greater than the length. Has O(n) complexity.
+	pub fn insert(&mut self, idx: usize, elem: T) {
+		let inverse = Edit::Insert(idx, elem).apply(&mut self.vec);
+		self.record(inverse);
+	}
+}
+
+impl<T: Clone> JournaledVector<T> {
+	///Removes the last element, keeping a clone in the journal.
+	///
+	/// Returns `None` if the vector is empty. Has O(1) complexity.
+	pub fn pop(&mut self) -> Option<T> {
+		let elem = self.vec.pop()?;
+		self.record(Edit::Push(elem.clone()));
+		Some(elem)
+	}
+
+	///Removes the element at `idx`, keeping a clone in the journal.
+	///
+	/// Panics if idx is out of bounds. Has O(n) complexity.
+	pub fn remove(&mut self, idx: usize) -> T {
+		let elem = self.vec.remove(idx);
+		self.record(Edit::Insert(idx, elem.clone()));
+		elem
+	}
+
+	///Replaces the element at `idx`, keeping the old element in the journal and returning a
+	/// clone of it.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(1).
+	pub fn set(&mut self, idx: usize, elem: T) -> T {
+		let inverse = Edit::Set(idx, elem).apply(&mut self.vec);
+		let old = match &inverse {
+			Edit::Set(_, old) => old.clone(),
+			_ => unreachable!("Set is reverted by a set"),
+		};
+		self.record(inverse);
+		old
+	}
+}
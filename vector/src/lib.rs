@@ -3,6 +3,7 @@ use std::{
 	alloc::Layout,
 	fmt,
 	fmt::Debug,
+	iter,
 	iter::FromIterator,
 	mem,
 	ops::{Index, IndexMut},
@@ -19,25 +20,160 @@ pub mod test_i32;
 #[cfg(test)]
 pub mod test_zst;
 
+#[cfg(test)]
+pub mod test_vector_2d;
+
+#[cfg(test)]
+pub mod test_soa;
+
+#[cfg(test)]
+pub mod test_aligned;
+
+#[cfg(test)]
+pub mod test_gap_buffer;
+
+#[cfg(test)]
+pub mod test_io;
+
+#[cfg(test)]
+pub mod test_pod;
+
+#[cfg(all(test, target_os = "linux"))]
+pub mod test_mmap;
+
+#[cfg(test)]
+pub mod test_cow;
+
+#[cfg(test)]
+pub mod test_concurrent;
+
+#[cfg(test)]
+pub mod test_observable;
+
+#[cfg(test)]
+pub mod test_journal;
+
+#[cfg(test)]
+pub mod test_index_vector;
+
+#[cfg(test)]
+pub mod test_jagged;
+
+#[cfg(test)]
+pub mod test_sparse;
+
+#[cfg(test)]
+pub mod test_packed;
+
+#[cfg(test)]
+pub mod test_pool;
+
+#[cfg(test)]
+pub mod test_panic;
+
+#[cfg(all(test, feature = "debug-invariants"))]
+pub mod test_invariants;
+
+#[cfg(test)]
+pub mod test_stats;
+
+#[cfg(test)]
+pub mod test_par;
+
+#[cfg(test)]
+pub mod test_extend;
+
+#[cfg(test)]
+pub mod test_disjoint_set;
+
 pub mod iterator;
 use iterator::{BorrowedVectorIterator, BorrowedVectorIteratorMut, VectorIterator};
 
+mod invariants;
+
+#[cfg(feature = "alloc-stats")]
+pub mod stats;
+#[cfg(feature = "alloc-stats")]
+pub use stats::AllocStats;
+
+pub mod vector_2d;
+pub use vector_2d::Vector2D;
+
+pub mod soa;
+
+pub mod gap_buffer;
+pub use gap_buffer::GapBuffer;
+
+pub mod io;
+pub use io::VectorCursor;
+
+pub mod pod;
+pub use pod::{Pod, PodError, PodKind};
+
+#[cfg(target_os = "linux")]
+pub mod mmap;
+#[cfg(target_os = "linux")]
+pub use mmap::MmapVector;
+
+pub mod cow;
+pub use cow::{ArcCowVector, CowVector};
+
+pub mod concurrent;
+pub use concurrent::ConcurrentVector;
+
+pub mod observable;
+pub use observable::ObservableVector;
+
+pub mod journal;
+pub use journal::JournaledVector;
+
+pub mod index_vector;
+pub use index_vector::{Idx, IndexVector};
+
+pub mod jagged;
+pub use jagged::JaggedVector;
+
+pub mod sparse;
+pub use sparse::SparseVector;
+
+pub mod packed;
+pub use packed::PackedIntVector;
+
+pub mod pool;
+pub use pool::{PooledVector, VectorPool};
+
+pub mod disjoint_set;
+pub use disjoint_set::DisjointSet;
+
+pub mod par;
+
+///A `Vector` whose buffer is aligned to at least `ALIGN` bytes across every reallocation,
+/// for use with SIMD loads and stores. `ALIGN` must be a power of two.
+pub type AlignedVector<T, const ALIGN: usize> = Vector<T, ALIGN>;
+
 const GROWTH_RATE: f64 = 1.25;
 
 ///A resizable contiguous array of `T`. Does not allocate upon creation.
-pub struct Vector<T> {
+///
+/// The buffer is aligned to `ALIGN` bytes or `T`'s own alignment, whichever is larger. The default
+/// of 0 leaves it at `T`'s alignment, see `AlignedVector` for over-aligned buffers.
+pub struct Vector<T, const ALIGN: usize = 0> {
 	pub(crate) data: Option<NonNull<T>>,
 	pub(crate) size: usize,
 	pub(crate) capacity: usize,
 }
 
-impl<T> Default for Vector<T> {
+//Safety: The vector owns its elements, so it can be sent or shared whenever they can.
+unsafe impl<T: Send, const ALIGN: usize> Send for Vector<T, ALIGN> {}
+unsafe impl<T: Sync, const ALIGN: usize> Sync for Vector<T, ALIGN> {}
+
+impl<T, const ALIGN: usize> Default for Vector<T, ALIGN> {
 	fn default() -> Self {
-		Self::new()
+		Self::new_aligned()
 	}
 }
 
-impl<T: Debug> Debug for Vector<T> {
+impl<T: Debug, const ALIGN: usize> Debug for Vector<T, ALIGN> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		if self.is_empty() {
 			return write!(f, "[]");
@@ -54,34 +190,42 @@ impl<T: Debug> Debug for Vector<T> {
 	}
 }
 
-impl<T> Index<usize> for Vector<T> {
+impl<T: Clone, const ALIGN: usize> Clone for Vector<T, ALIGN> {
+	fn clone(&self) -> Self {
+		let mut vec = Vector::with_capacity_aligned(self.size);
+		for elem in self.iter() {
+			vec.push(elem.clone());
+		}
+		vec
+	}
+}
+
+impl<T, const ALIGN: usize> Index<usize> for Vector<T, ALIGN> {
 	type Output = T;
 	fn index(&self, index: usize) -> &Self::Output {
 		self.get(index).expect("Index was out of bounds")
 	}
 }
 
-impl<T> IndexMut<usize> for Vector<T> {
+impl<T, const ALIGN: usize> IndexMut<usize> for Vector<T, ALIGN> {
 	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
 		self.get_mut(index).expect("Index was out of bounds")
 	}
 }
 
-impl<T> IntoIterator for Vector<T> {
+impl<T, const ALIGN: usize> IntoIterator for Vector<T, ALIGN> {
 	type Item = T;
 
-	type IntoIter = VectorIterator<T>;
+	type IntoIter = VectorIterator<T, ALIGN>;
 
-	fn into_iter(mut self) -> Self::IntoIter {
+	fn into_iter(self) -> Self::IntoIter {
 		let Vector {
 			data,
 			capacity,
 			size,
 		} = self;
-		//Moves the pointer out of the vector so that the allocation
-		// won't be freed at the end of this block.
-		self.data = None;
-		self.size = 0;
+		//The iterator takes over the allocation, so the vector must not free it.
+		mem::forget(self);
 		VectorIterator {
 			data,
 			capacity,
@@ -91,24 +235,24 @@ impl<T> IntoIterator for Vector<T> {
 	}
 }
 
-impl<'a, T> IntoIterator for &'a Vector<T> {
+impl<'a, T, const ALIGN: usize> IntoIterator for &'a Vector<T, ALIGN> {
 	type Item = &'a T;
 
-	type IntoIter = BorrowedVectorIterator<'a, T>;
+	type IntoIter = BorrowedVectorIterator<'a, T, ALIGN>;
 
 	fn into_iter(self) -> Self::IntoIter {
 		BorrowedVectorIterator {
-			vector: &self,
+			vector: self,
 			index: -1isize as usize,
 			index_back: self.size,
 		}
 	}
 }
 
-impl<'a, T> IntoIterator for &'a mut Vector<T> {
+impl<'a, T, const ALIGN: usize> IntoIterator for &'a mut Vector<T, ALIGN> {
 	type Item = &'a mut T;
 
-	type IntoIter = BorrowedVectorIteratorMut<'a, T>;
+	type IntoIter = BorrowedVectorIteratorMut<'a, T, ALIGN>;
 
 	fn into_iter(self) -> Self::IntoIter {
 		let size = self.size;
@@ -120,29 +264,91 @@ impl<'a, T> IntoIterator for &'a mut Vector<T> {
 	}
 }
 
-impl<T> FromIterator<T> for Vector<T> {
+impl<T, const ALIGN: usize> FromIterator<T> for Vector<T, ALIGN> {
 	fn from_iter<A: IntoIterator<Item = T>>(iter: A) -> Self {
-		let iter = iter.into_iter();
+		let mut vec = Vector::new_aligned();
+		vec.extend(iter);
+		vec
+	}
+}
+
+impl<T, const ALIGN: usize> Extend<T> for Vector<T, ALIGN> {
+	///Reserves space for the lower bound of the iterator's size hint once, writes elements
+	/// straight into it and pushes whatever the hint didn't cover.
+	fn extend<A: IntoIterator<Item = T>>(&mut self, iter: A) {
+		let mut iter = iter.into_iter();
 		let (min, _) = iter.size_hint();
-		let mut vec = Vector::with_capacity(min);
-		for item in iter {
-			vec.push(item);
+		self.grow_for(min);
+		if let Some(ptr) = self.as_ptr_mut() {
+			let spare = self.capacity - self.size;
+			let mut guard = SetLenOnDrop {
+				len: self.size,
+				size: &mut self.size,
+			};
+			for elem in iter.by_ref().take(spare) {
+				//Safety: At most `spare` elements are written past the length, within the capacity.
+				unsafe { ptr.add(guard.len).write(elem) };
+				guard.len += 1;
+			}
 		}
-		vec
+		for elem in iter {
+			self.push(elem);
+		}
+		self.debug_check();
+	}
+}
+
+impl<'a, T: Copy + 'a, const ALIGN: usize> Extend<&'a T> for Vector<T, ALIGN> {
+	fn extend<A: IntoIterator<Item = &'a T>>(&mut self, iter: A) {
+		self.extend(iter.into_iter().copied());
+	}
+}
+
+impl<T, const N: usize, const ALIGN: usize> From<[T; N]> for Vector<T, ALIGN> {
+	fn from(array: [T; N]) -> Self {
+		IntoIterator::into_iter(array).collect()
+	}
+}
+
+///Stores the length of elements written into spare capacity when dropped, so they're kept in the
+/// vector even if producing the next one panics.
+struct SetLenOnDrop<'a> {
+	size: &'a mut usize,
+	len: usize,
+}
+
+impl<'a> Drop for SetLenOnDrop<'a> {
+	fn drop(&mut self) {
+		*self.size = self.len;
 	}
 }
 
-impl<T> Drop for Vector<T> {
+impl<T, const ALIGN: usize> Drop for Vector<T, ALIGN> {
 	fn drop(&mut self) {
-		//Outside the loop to handle zero size types
+		//Frees the buffer after the elements are dropped, even if one of them panics.
+		let _guard = DeallocGuard::<T, ALIGN> {
+			data: self.data,
+			capacity: self.capacity,
+		};
 		self.clear();
+	}
+}
+
+///Frees a vector's buffer when dropped. Held while dropping elements so the buffer isn't
+/// leaked if a drop panics.
+pub(crate) struct DeallocGuard<T, const ALIGN: usize> {
+	pub(crate) data: Option<NonNull<T>>,
+	pub(crate) capacity: usize,
+}
+
+impl<T, const ALIGN: usize> Drop for DeallocGuard<T, ALIGN> {
+	fn drop(&mut self) {
 		if let Some(ptr) = self.data {
-			let ptr = ptr.as_ptr();
-			let layout = Layout::array::<T>(self.capacity)
-				.expect("Cannot recreate layout. Has capacity been changed?");
 			//Safety: Capacity is only changed on reallocation, pointer is trusted
-			// and iterators return to vectors for deallocation.
-			unsafe { alloc::dealloc(ptr as *mut u8, layout) }
+			// and iterators hand the buffer to this guard for deallocation.
+			unsafe { Vector::<T, ALIGN>::deallocate(ptr, self.capacity) };
+			#[cfg(feature = "alloc-stats")]
+			stats::record_dealloc(Vector::<T, ALIGN>::layout(self.capacity).size());
 		}
 	}
 }
@@ -150,6 +356,55 @@ impl<T> Drop for Vector<T> {
 impl<T> Vector<T> {
 	///Creates a new vector. Does not allocate till it's needed.
 	pub fn new() -> Self {
+		Vector::new_aligned()
+	}
+
+	///Creates a new vector with a preallocated buffer with space for `cap` elements.
+	pub fn with_capacity(cap: usize) -> Self {
+		Vector::with_capacity_aligned(cap)
+	}
+
+	///Creates a vector of `n` clones of `elem`, allocating once. Used by `vector![elem; n]`.
+	///
+	/// Has O(n) complexity.
+	pub fn from_elem(elem: T, n: usize) -> Self
+	where
+		T: Clone,
+	{
+		iter::repeat_n(elem, n).collect()
+	}
+}
+
+///Creates a `Vector` from a list of elements, like `vec!`.
+///
+/// ```
+/// use vector::{vector, Vector};
+///
+/// let list = vector![1, 2, 3];
+/// assert_eq!(list.as_slice(), &[1, 2, 3]);
+/// let zeroes = vector![0u8; 4];
+/// assert_eq!(zeroes.as_slice(), &[0, 0, 0, 0]);
+/// let empty: Vector<i32> = vector![];
+/// assert!(empty.is_empty());
+/// ```
+///
+/// `vector![elem; n]` clones `elem`, so `elem` is evaluated once.
+#[macro_export]
+macro_rules! vector {
+	() => {
+		$crate::Vector::new()
+	};
+	($elem:expr; $n:expr) => {
+		$crate::Vector::from_elem($elem, $n)
+	};
+	($($elem:expr),+ $(,)?) => {
+		<$crate::Vector<_>>::from([$($elem),+])
+	};
+}
+
+impl<T, const ALIGN: usize> Vector<T, ALIGN> {
+	///Creates a new vector with the alignment given by the type. Does not allocate till it's needed.
+	pub fn new_aligned() -> Self {
 		let capacity = if mem::size_of::<T>() == 0 {
 			usize::MAX
 		} else {
@@ -162,10 +417,12 @@ impl<T> Vector<T> {
 		}
 	}
 
-	///Creates a new vector with a preallocated buffer with space for `cap` elements.
-	pub fn with_capacity(cap: usize) -> Self {
-		let mut vec = Vector::new();
-		if mem::size_of::<T>() != 0 {
+	///Creates a new vector with the alignment given by the type and a preallocated buffer
+	/// with space for `cap` elements.
+	pub fn with_capacity_aligned(cap: usize) -> Self {
+		let mut vec = Vector::new_aligned();
+		//Zero sized allocations aren't allowed, so an empty request waits for the first push.
+		if mem::size_of::<T>() != 0 && cap != 0 {
 			vec.reserve(cap);
 		}
 		vec
@@ -181,6 +438,33 @@ impl<T> Vector<T> {
 		self.size
 	}
 
+	///Returns the amount of elements the vector can hold without reallocating.
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+
+	///Returns the bytes of the vector's heap buffer, which is 0 until it first allocates and for
+	/// zero sized types. Doesn't include heap memory owned by the elements.
+	///
+	/// Has complexity O(1).
+	pub fn allocated_bytes(&self) -> usize {
+		match self.data {
+			Some(_) => Self::layout(self.capacity).size(),
+			None => 0,
+		}
+	}
+
+	///Returns the layout of a buffer with space for `cap` elements, aligned to `ALIGN` or the
+	/// alignment of `T`, whichever is larger.
+	///
+	/// Panics if the size overflows or `ALIGN` is neither 0 nor a power of two.
+	pub(crate) fn layout(cap: usize) -> Layout {
+		Layout::array::<T>(cap)
+			.expect("Overflow")
+			.align_to(ALIGN.max(mem::align_of::<T>()))
+			.expect("ALIGN must be a power of two")
+	}
+
 	///Allocates a new buffer for the vector of specified size.
 	///
 	/// Panics if `new_cap` is smaller than current size or overflows a `usize`. Has O(n) complexity.
@@ -190,29 +474,27 @@ impl<T> Vector<T> {
 			0,
 			"Vector currently doesn't support storing 0 sized types"
 		);
-		let layout = Layout::array::<T>(new_cap).expect("Overflow");
-		//Safety: Layout is type and capacity checked.
-		let new_ptr = unsafe { alloc::alloc(layout) as *mut T };
 		assert!(
 			new_cap >= self.size,
 			"New capacity can't contain current vector"
 		);
-		assert!(!new_ptr.is_null());
-		let new_data = NonNull::new(new_ptr);
+		let new_data = Self::allocate(new_cap);
+		#[cfg(feature = "alloc-stats")]
+		stats::record_alloc(Self::layout(new_cap).size(), self.data.is_some());
+		let new_ptr = new_data.as_ptr();
 		if let Some(old_ptr) = self.data {
 			unsafe {
 				//Safety: The new allocation is a seperate allocation, so the copy is guaranteed to not overlap.
 				ptr::copy_nonoverlapping(old_ptr.as_ptr(), new_ptr, self.size);
 				//Safety: The pointer is only changed here in allocation.
-				alloc::dealloc(
-					old_ptr.as_ptr() as *mut u8,
-					Layout::array::<T>(self.capacity)
-						.expect("Cannot recreate layout? Has capacity been edited?"),
-				);
+				Self::deallocate(old_ptr, self.capacity);
+				#[cfg(feature = "alloc-stats")]
+				stats::record_dealloc(Self::layout(self.capacity).size());
 			}
 		}
-		self.data = new_data;
+		self.data = Some(new_data);
 		self.capacity = new_cap;
+		self.debug_check();
 	}
 
 	///Allocates a new buffer for the vector that is larger by `additional` elements.
@@ -258,6 +540,36 @@ impl<T> Vector<T> {
 				.write(elem)
 		};
 		self.size += 1;
+		self.debug_check();
+	}
+
+	///Grows the buffer to fit `additional` more elements if it doesn't already, by at least the
+	/// growth rate so repeated calls stay amortised.
+	///
+	/// Panics if the length of the vector overflows a `usize`.
+	fn grow_for(&mut self, additional: usize) {
+		if mem::size_of::<T>() == 0 || additional == 0 {
+			return;
+		}
+		let new_size = self
+			.size
+			.checked_add(additional)
+			.expect("New size overflowed usize");
+		if new_size > self.capacity {
+			let grown = (self.capacity as f64 * GROWTH_RATE).ceil() as usize;
+			self.reserve(new_size.max(grown));
+		}
+	}
+
+	///Clones every element of `other` to the back of the vector, reallocating at most once. If a
+	/// clone panics the elements cloned before it are kept.
+	///
+	/// Panics if the length of the vector overflows a `usize`. Has O(n) complexity in the length of `other`.
+	pub fn extend_from_slice(&mut self, other: &[T])
+	where
+		T: Clone,
+	{
+		self.extend(other.iter().cloned());
 	}
 
 	///Gets a reference to the element at index's position.
@@ -285,8 +597,10 @@ impl<T> Vector<T> {
 	///Inserts element in vector at index, moving everything after it to the right.
 	/// Will reallocate if length equals capacity.
 	///
-	/// Panics if the vector's length will overflow `usize::MAX`. Has O(n) complexity.
+	/// Panics if idx is greater than the length or the vector's length will overflow `usize::MAX`.
+	/// Has O(n) complexity.
 	pub fn insert(&mut self, idx: usize, elem: T) {
+		assert!(idx <= self.size, "Index was out of bounds");
 		if idx == self.size {
 			return self.push(elem);
 		}
@@ -318,6 +632,7 @@ impl<T> Vector<T> {
 		unsafe { data_ptr.add(idx).write(elem) };
 
 		self.size += 1;
+		self.debug_check();
 	}
 
 	///Removes the last element in the vector
@@ -331,7 +646,9 @@ impl<T> Vector<T> {
 		self.size -= 1;
 		let data_ptr = self.as_ptr_mut()?;
 		//Safety: Existing pointer is trusted.
-		Some(unsafe { data_ptr.add(self.size).read() })
+		let elem = unsafe { data_ptr.add(self.size).read() };
+		self.debug_check();
+		Some(elem)
 	}
 
 	///Removes the item at index, moving everything after that by one step to the left.
@@ -362,6 +679,7 @@ impl<T> Vector<T> {
 		}
 
 		self.size -= 1;
+		self.debug_check();
 		ret
 	}
 
@@ -369,9 +687,15 @@ impl<T> Vector<T> {
 	///
 	/// Has O(n) complexity.
 	pub fn clear(&mut self) {
-		while !self.is_empty() {
-			self.pop();
+		let len = self.size;
+		//The length is reset first so a panicking drop can't leave dropped elements within it.
+		// The remaining elements are still dropped while unwinding.
+		self.size = 0;
+		if let Some(ptr) = self.as_ptr_mut() {
+			//Safety: Every element up to the old length is initialised and no longer reachable.
+			unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr, len)) };
 		}
+		self.debug_check();
 	}
 
 	///Borrows the vector's allocation as an immutable slice.
@@ -428,19 +752,20 @@ impl<T> Vector<T> {
 			panic!();
 		}
 		self.size = len;
+		self.debug_check();
 	}
 
 	///Returns an iterator over borrowed elements of the vector.
 	///
 	/// Has complexity O(1).
-	pub fn iter(&self) -> BorrowedVectorIterator<'_, T> {
-		(&self).into_iter()
+	pub fn iter(&self) -> BorrowedVectorIterator<'_, T, ALIGN> {
+		self.into_iter()
 	}
 
 	///Returns an iterator over mutably borrowed elements of the vector.
 	///
 	/// Has complexity O(1).
-	pub fn iter_mut(&mut self) -> BorrowedVectorIteratorMut<'_, T> {
+	pub fn iter_mut(&mut self) -> BorrowedVectorIteratorMut<'_, T, ALIGN> {
 		(self).into_iter()
 	}
 
@@ -450,7 +775,7 @@ impl<T> Vector<T> {
 	/// Has complexity O(1).
 	pub fn as_ptr(&self) -> Option<*const T> {
 		if mem::size_of::<T>() == 0 {
-			Some(self as *const Vector<T> as *const T)
+			Some(self as *const Vector<T, ALIGN> as *const T)
 		} else {
 			self.data.map(|p| p.as_ptr() as *const _)
 		}
@@ -462,7 +787,7 @@ impl<T> Vector<T> {
 	/// Has complexity O(1).
 	pub fn as_ptr_mut(&mut self) -> Option<*mut T> {
 		if mem::size_of::<T>() == 0 {
-			Some(self as *mut Vector<T> as *mut T)
+			Some(self as *mut Vector<T, ALIGN> as *mut T)
 		} else {
 			self.data.map(|p| p.as_ptr())
 		}
@@ -474,39 +799,71 @@ impl<T> Vector<T> {
 	///
 	/// Has complexity O(n)
 	pub fn retain(&mut self, f: fn(&T) -> bool) {
-		if mem::size_of::<T>() == 0 {
-			for i in (0..self.size).rev() {
-				//Even if there is no data and the function can't actually depend
-				// on the value of the element, the function might not be pure,
-				// hence looping instead of one check and do nothing/clear all.
-				if f(&self[i]) {
-					self.pop();
-				}
+		let len = self.size;
+		//The length is zero while elements are moved, so a panic in `f` or a drop can't expose
+		// holes. The guard closes the gap and sets the real length even then.
+		self.size = 0;
+		let ptr = match self.as_ptr_mut() {
+			Some(ptr) => ptr,
+			None => return,
+		};
+		let mut guard = RetainGuard {
+			vec: self,
+			processed: 0,
+			deleted: 0,
+			len,
+		};
+		while guard.processed < len {
+			//Safety: Processed elements are either moved back or dropped, the rest are untouched.
+			let cur = unsafe { &mut *ptr.add(guard.processed) };
+			if !f(cur) {
+				//Counted first so the element isn't dropped twice if its drop panics.
+				guard.processed += 1;
+				guard.deleted += 1;
+				//Safety: The element is no longer reachable after this.
+				unsafe { ptr::drop_in_place(cur) };
+				continue;
 			}
-			return;
-		}
-		if self.data.is_none() {
-			return;
-		}
-		let ptr = self.data.expect("Above check failed?").as_ptr();
-		let mut back = 0;
-		for front in 0..self.size {
-			let ok = f(&self[front]);
-			if ok {
-				if back != front {
-					//Safety: Element is moved within the allocated space (as front is
-					// always greater than back and front is bound by size) without extra
-					// copies or clones which would be required as you otherwise can't move
-					// out of a vector. The element which was overwritten had already been
-					// moved or dropped.
-					unsafe { ptr.add(back).write(ptr.add(front).read()) };
-					back += 1;
-				}
-			} else {
-				//Make sure drop is run and the element is not just left to be overwritten.
-				let _ = unsafe { ptr.add(front).read() };
+			if guard.deleted > 0 {
+				//Safety: Moves the element back over one which was dropped or moved already.
+				unsafe {
+					ptr::copy_nonoverlapping(
+						ptr.add(guard.processed),
+						ptr.add(guard.processed - guard.deleted),
+						1,
+					)
+				};
 			}
+			guard.processed += 1;
+		}
+		drop(guard);
+		self.debug_check();
+	}
+}
+
+///Shifts the elements `retain` hasn't looked at yet over the removed ones and sets the length,
+/// also when `retain` unwinds.
+struct RetainGuard<'a, T, const ALIGN: usize> {
+	vec: &'a mut Vector<T, ALIGN>,
+	processed: usize,
+	deleted: usize,
+	len: usize,
+}
+
+impl<'a, T, const ALIGN: usize> Drop for RetainGuard<'a, T, ALIGN> {
+	fn drop(&mut self) {
+		if self.deleted > 0 {
+			let ptr = self.vec.as_ptr_mut().expect("Retain without a buffer?");
+			//Safety: The unprocessed elements are initialised and the gap in front of them
+			// holds only dropped or moved elements.
+			unsafe {
+				ptr::copy(
+					ptr.add(self.processed),
+					ptr.add(self.processed - self.deleted),
+					self.len - self.processed,
+				)
+			};
 		}
-		self.size = back;
+		self.vec.size = self.len - self.deleted;
 	}
 }
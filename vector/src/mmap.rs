@@ -0,0 +1,317 @@
+use crate::{
+	pod::{read_header, write_header, HEADER_LEN},
+	*,
+};
+use std::{
+	convert::TryFrom,
+	fs::{File, OpenOptions},
+	io,
+	marker::PhantomData,
+	os::unix::io::AsRawFd,
+	path::Path,
+	slice,
+	sync::atomic::{AtomicU64, Ordering},
+};
+
+///Identifies a file as a memory mapped vector, the last two bytes are the format version.
+const MAGIC: [u8; 8] = *b"VECMAP01";
+///The elements start one page in, so the header can be synced on its own.
+const DATA_OFFSET: usize = 4096;
+///Byte offset of the length within the header.
+const LEN_OFFSET: usize = 24;
+
+///A resizable contiguous array of `T` stored in a memory mapped file, so it can be larger than
+/// memory and persists between runs.
+///
+/// The file starts with a header page holding the element type and the length, followed by the
+/// elements. The length is only updated after an element is written, so if the process crashes
+/// the file never exposes an element that wasn't fully written.
+///
+/// That doesn't hold if the machine crashes or loses power. The kernel writes dirty pages back in
+/// any order, so the header page may reach the disk before the data it covers. Call `flush`, which
+/// syncs the elements before the header, to make changes durable.
+pub struct MmapVector<T: Pod> {
+	pub(crate) file: File,
+	pub(crate) map: NonNull<u8>,
+	pub(crate) map_len: usize,
+	pub(crate) size: usize,
+	pub(crate) _marker: PhantomData<T>,
+}
+
+impl<T: Pod + Debug> Debug for MmapVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl<T: Pod> Index<usize> for MmapVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T: Pod> IndexMut<usize> for MmapVector<T> {
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		self.get_mut(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T: Pod> Drop for MmapVector<T> {
+	fn drop(&mut self) {
+		//Safety: The mapping is owned by this vector and no borrows of it can outlive it.
+		// The kernel writes back the dirty pages on its own after unmapping.
+		unsafe { libc::munmap(self.map.as_ptr() as *mut libc::c_void, self.map_len) };
+	}
+}
+
+impl<T: Pod> MmapVector<T> {
+	///Creates a new empty vector in a new file at `path`, replacing any existing file.
+	///
+	/// Panics if `T` is zero sized or aligned to more than a page.
+	pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		Self::check_type();
+		let file = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.create(true)
+			.truncate(true)
+			.open(path)?;
+		file.set_len(DATA_OFFSET as u64)?;
+		let vec = Self::map(file, DATA_OFFSET, 0)?;
+		let mut header = [0; HEADER_LEN];
+		write_header::<T>(&mut header, MAGIC, 0);
+		//Safety: The header fits within the first page of the fresh mapping.
+		unsafe { ptr::copy_nonoverlapping(header.as_ptr(), vec.map.as_ptr(), HEADER_LEN) };
+		Ok(vec)
+	}
+
+	///Opens a vector previously created with `create`.
+	///
+	/// Returns an `InvalidData` error holding a `PodError` if the file holds a different type
+	/// or its length doesn't fit in the file. Panics if `T` is zero sized or aligned to more
+	/// than a page.
+	pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+		Self::check_type();
+		let file = OpenOptions::new().read(true).write(true).open(path)?;
+		let file_len = usize::try_from(file.metadata()?.len()).map_err(|_| PodError::Length)?;
+		if file_len < DATA_OFFSET {
+			return Err(PodError::Magic.into());
+		}
+		let mut vec = Self::map(file, file_len, 0)?;
+		let mut header = [0; HEADER_LEN];
+		//Safety: The file, and so the mapping, is at least a page long.
+		unsafe { ptr::copy_nonoverlapping(vec.map.as_ptr(), header.as_mut_ptr(), HEADER_LEN) };
+		let len =
+			usize::try_from(read_header::<T>(&header, MAGIC)?).map_err(|_| PodError::Length)?;
+		if len > vec.capacity() {
+			return Err(PodError::Length.into());
+		}
+		vec.size = len;
+		Ok(vec)
+	}
+
+	fn check_type() {
+		assert_ne!(
+			mem::size_of::<T>(),
+			0,
+			"MmapVector doesn't support storing 0 sized types"
+		);
+		assert!(
+			mem::align_of::<T>() <= DATA_OFFSET,
+			"MmapVector doesn't support types aligned to more than a page"
+		);
+	}
+
+	fn map(file: File, map_len: usize, size: usize) -> io::Result<Self> {
+		//Safety: A fresh shared mapping of the whole file, the result is checked below.
+		let ptr = unsafe {
+			libc::mmap(
+				ptr::null_mut(),
+				map_len,
+				libc::PROT_READ | libc::PROT_WRITE,
+				libc::MAP_SHARED,
+				file.as_raw_fd(),
+				0,
+			)
+		};
+		if ptr == libc::MAP_FAILED {
+			return Err(io::Error::last_os_error());
+		}
+		Ok(MmapVector {
+			file,
+			map: NonNull::new(ptr as *mut u8).expect("mmap returned null?"),
+			map_len,
+			size,
+			_marker: PhantomData,
+		})
+	}
+
+	///Checks if the vector has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.size == 0
+	}
+
+	///Returns the amount of elements stored in the vector.
+	pub fn len(&self) -> usize {
+		self.size
+	}
+
+	///Returns the amount of elements that fit in the file without growing it.
+	pub fn capacity(&self) -> usize {
+		(self.map_len - DATA_OFFSET) / mem::size_of::<T>()
+	}
+
+	fn data_ptr(&self) -> *mut T {
+		//Safety: The mapping is always at least one page long. Pages are aligned for any `T`
+		// accepted by `check_type`.
+		unsafe { self.map.as_ptr().add(DATA_OFFSET) as *mut T }
+	}
+
+	///Stores the length in the header. Uses release ordering so the elements are written
+	/// to the mapping before the length that covers them. This orders the writes in memory only,
+	/// not on disk, see `flush`.
+	fn store_len(&mut self) {
+		//Safety: The length is 8 byte aligned within the page aligned mapping and only
+		// accessed through this atomic.
+		let len = unsafe { &*(self.map.as_ptr().add(LEN_OFFSET) as *const AtomicU64) };
+		len.store(self.size as u64, Ordering::Release);
+	}
+
+	///Grows the file and mapping to fit at least `new_cap` elements.
+	///
+	/// Has O(1) complexity, the kernel moves the mapping without copying if needed.
+	fn reserve(&mut self, new_cap: usize) -> io::Result<()> {
+		let new_len = new_cap
+			.checked_mul(mem::size_of::<T>())
+			.and_then(|bytes| bytes.checked_add(DATA_OFFSET))
+			.expect("Overflow");
+		self.file.set_len(new_len as u64)?;
+		//Safety: Remaps the mapping owned by this vector, no references into it exist while
+		// `self` is mutably borrowed.
+		let ptr = unsafe {
+			libc::mremap(
+				self.map.as_ptr() as *mut libc::c_void,
+				self.map_len,
+				new_len,
+				libc::MREMAP_MAYMOVE,
+			)
+		};
+		if ptr == libc::MAP_FAILED {
+			return Err(io::Error::last_os_error());
+		}
+		self.map = NonNull::new(ptr as *mut u8).expect("mremap returned null?");
+		self.map_len = new_len;
+		Ok(())
+	}
+
+	///Inserts an element at the back of the vector, growing the file if it's full.
+	///
+	/// Returns an error if the file can't be grown. Has complexity O(1).
+	pub fn push(&mut self, elem: T) -> io::Result<()> {
+		if self.size == self.capacity() {
+			let grown = (self.capacity() as f64 * GROWTH_RATE).ceil() as usize;
+			//Grow by at least a page worth of elements so small vectors don't remap on every push.
+			let min = self.capacity() + (DATA_OFFSET / mem::size_of::<T>()).max(1);
+			self.reserve(grown.max(min))?;
+		}
+		//Safety: Length is checked. If the file was already full it is grown above.
+		unsafe { self.data_ptr().add(self.size).write(elem) };
+		self.size += 1;
+		self.store_len();
+		Ok(())
+	}
+
+	///Removes the last element in the vector. The file keeps its size.
+	///
+	/// Returns `None` if the vector is empty. Has O(1) complexity.
+	pub fn pop(&mut self) -> Option<T> {
+		if self.size == 0 {
+			return None;
+		}
+		self.size -= 1;
+		self.store_len();
+		//Safety: The element was within the length and `Pod` types can be copied out freely.
+		Some(unsafe { self.data_ptr().add(self.size).read() })
+	}
+
+	///Removes every element in the vector. The file keeps its size.
+	///
+	/// Has O(1) complexity.
+	pub fn clear(&mut self) {
+		self.size = 0;
+		self.store_len();
+	}
+
+	///Gets a reference to the element at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has complexity O(1).
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		self.as_slice().get(idx)
+	}
+
+	///Gets a mutable reference to the element at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has complexity O(1).
+	pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+		self.as_slice_mut().get_mut(idx)
+	}
+
+	///Borrows the mapped elements as an immutable slice.
+	///
+	/// Has complexity O(1).
+	pub fn as_slice(&self) -> &[T] {
+		//Safety: Every element up to the length is initialised and within the mapping.
+		unsafe { slice::from_raw_parts(self.data_ptr(), self.size) }
+	}
+
+	///Borrows the mapped elements as a mutable slice.
+	///
+	/// Has complexity O(1).
+	pub fn as_slice_mut(&mut self) -> &mut [T] {
+		//Safety: Every element up to the length is initialised and within the mapping.
+		unsafe { slice::from_raw_parts_mut(self.data_ptr(), self.size) }
+	}
+
+	///Returns an iterator over borrowed elements of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> slice::Iter<'_, T> {
+		self.as_slice().iter()
+	}
+
+	///Returns an iterator over mutably borrowed elements of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+		self.as_slice_mut().iter_mut()
+	}
+
+	///Writes every change to the file and waits for it to reach the disk. The elements are synced
+	/// before the header so the stored length never covers elements that didn't make it.
+	///
+	/// Has O(n) complexity in the amount of changed pages.
+	pub fn flush(&self) -> io::Result<()> {
+		//msync needs page aligned addresses, with large pages the data shares one with the header.
+		let data_start = DATA_OFFSET - DATA_OFFSET % page_size();
+		for (start, len) in [(data_start, self.map_len - data_start), (0, DATA_OFFSET)] {
+			//Safety: Both ranges are page aligned and within the mapping.
+			let res = unsafe {
+				libc::msync(
+					self.map.as_ptr().add(start) as *mut libc::c_void,
+					len,
+					libc::MS_SYNC,
+				)
+			};
+			if res != 0 {
+				return Err(io::Error::last_os_error());
+			}
+		}
+		Ok(())
+	}
+}
+
+fn page_size() -> usize {
+	//Safety: Just a query, with no preconditions.
+	unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
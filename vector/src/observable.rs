@@ -0,0 +1,232 @@
+use crate::*;
+use std::ops::Deref;
+
+///A change made to an `ObservableVector`. Indices are positions at the time of the change, so
+/// replaying the changes in order against a copy keeps it in sync.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<T> {
+	///An element was inserted at `index`, shifting later elements back.
+	Inserted { index: usize },
+	///The element at `index` was removed, shifting later elements forward.
+	Removed { index: usize, value: T },
+	///The element at `index` may have been changed in place.
+	Updated { index: usize },
+	///Every element was removed at once.
+	Reset,
+}
+
+///Handle to a registered listener, used to remove it again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ListenerId(usize);
+
+type Listener<T> = Box<dyn FnMut(&Change<T>)>;
+
+///Wraps a `Vector` and reports every change made through it to registered listeners and,
+/// if enabled, a change log.
+///
+/// Reading goes through `Deref` to a slice. Every mutation is one of the methods below, which
+/// keeps the listeners from missing changes.
+pub struct ObservableVector<T> {
+	pub(crate) vec: Vector<T>,
+	pub(crate) listeners: Vector<Option<Listener<T>>>,
+	pub(crate) log: Option<Vector<Change<T>>>,
+}
+
+impl<T> Default for ObservableVector<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for ObservableVector<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Debug::fmt(&self.vec, f)
+	}
+}
+
+impl<T> Deref for ObservableVector<T> {
+	type Target = [T];
+
+	fn deref(&self) -> &Self::Target {
+		self.vec.as_slice()
+	}
+}
+
+impl<T> Index<usize> for ObservableVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		&self.vec[index]
+	}
+}
+
+impl<T> IndexMut<usize> for ObservableVector<T> {
+	///Reports `Updated` when the element is borrowed, as the write itself can't be observed.
+	fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+		self.get_mut(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T> From<Vector<T>> for ObservableVector<T> {
+	fn from(vec: Vector<T>) -> Self {
+		ObservableVector {
+			vec,
+			listeners: Vector::new(),
+			log: None,
+		}
+	}
+}
+
+impl<T> From<ObservableVector<T>> for Vector<T> {
+	fn from(vec: ObservableVector<T>) -> Self {
+		vec.vec
+	}
+}
+
+impl<T> ObservableVector<T> {
+	///Creates a new empty vector without listeners. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Vector::new().into()
+	}
+
+	///Registers a listener which is called with every following change.
+	pub fn subscribe<F: FnMut(&Change<T>) + 'static>(&mut self, listener: F) -> ListenerId {
+		self.listeners.push(Some(Box::new(listener)));
+		ListenerId(self.listeners.len() - 1)
+	}
+
+	///Removes a listener. Returns false if it was already removed.
+	pub fn unsubscribe(&mut self, id: ListenerId) -> bool {
+		match self.listeners.get_mut(id.0) {
+			Some(listener) => listener.take().is_some(),
+			None => false,
+		}
+	}
+
+	///Starts collecting every following change into a log, see `take_changes`.
+	pub fn record_changes(&mut self) {
+		if self.log.is_none() {
+			self.log = Some(Vector::new());
+		}
+	}
+
+	///Returns the changes collected since the last call, leaving the log empty. Returns an empty
+	/// vector if `record_changes` hasn't been called.
+	pub fn take_changes(&mut self) -> Vector<Change<T>> {
+		match &mut self.log {
+			Some(log) => mem::take(log),
+			None => Vector::new(),
+		}
+	}
+
+	fn emit(&mut self, change: Change<T>) {
+		for listener in self.listeners.iter_mut().flatten() {
+			listener(&change);
+		}
+		if let Some(log) = &mut self.log {
+			log.push(change);
+		}
+	}
+
+	///Borrows the wrapped vector.
+	pub fn as_vector(&self) -> &Vector<T> {
+		&self.vec
+	}
+
+	///Returns the wrapped vector, dropping the listeners and change log.
+	pub fn into_vector(self) -> Vector<T> {
+		self.vec
+	}
+
+	///Inserts an element at the back of the vector and reports `Inserted`.
+	///
+	/// Has complexity O(1).
+	pub fn push(&mut self, elem: T) {
+		self.vec.push(elem);
+		let index = self.vec.len() - 1;
+		self.emit(Change::Inserted { index });
+	}
+
+	///Removes the last element and reports `Removed` with a clone of it.
+	///
+	/// Returns `None` if the vector is empty. Has O(1) complexity.
+	pub fn pop(&mut self) -> Option<T>
+	where
+		T: Clone,
+	{
+		let elem = self.vec.pop()?;
+		let index = self.vec.len();
+		self.emit(Change::Removed {
+			index,
+			value: elem.clone(),
+		});
+		Some(elem)
+	}
+
+	///Inserts an element at `idx` and reports `Inserted`.
+	///
+	/// Panics if idx is greater than the length. Has O(n) complexity.
+	pub fn insert(&mut self, idx: usize, elem: T) {
+		self.vec.insert(idx, elem);
+		self.emit(Change::Inserted { index: idx });
+	}
+
+	///Removes the element at `idx` and reports `Removed` with a clone of it.
+	///
+	/// Panics if idx is out of bounds. Has O(n) complexity.
+	pub fn remove(&mut self, idx: usize) -> T
+	where
+		T: Clone,
+	{
+		let elem = self.vec.remove(idx);
+		self.emit(Change::Removed {
+			index: idx,
+			value: elem.clone(),
+		});
+		elem
+	}
+
+	///Removes every element for which `f` returns false, reporting one `Removed` per element
+	/// in order. Each index accounts for the removals reported before it.
+	///
+	/// Has O(n) complexity.
+	pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+		let old = mem::take(&mut self.vec);
+		self.vec = Vector::with_capacity(old.len());
+		for elem in old {
+			if f(&elem) {
+				self.vec.push(elem);
+			} else {
+				let index = self.vec.len();
+				self.emit(Change::Removed { index, value: elem });
+			}
+		}
+	}
+
+	///Removes every element and reports a single `Reset`.
+	///
+	/// Has O(n) complexity.
+	pub fn clear(&mut self) {
+		self.vec.clear();
+		self.emit(Change::Reset);
+	}
+
+	///Gets a mutable reference to the element at index's position and reports `Updated`.
+	///
+	/// Returns `None` without reporting if index is out of bounds. Has complexity O(1).
+	pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+		if idx >= self.vec.len() {
+			return None;
+		}
+		self.emit(Change::Updated { index: idx });
+		self.vec.get_mut(idx)
+	}
+
+	///Replaces the element at `idx`, reports `Updated` and returns the old element.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(1).
+	pub fn set(&mut self, idx: usize, elem: T) -> T {
+		let old = mem::replace(&mut self.vec[idx], elem);
+		self.emit(Change::Updated { index: idx });
+		old
+	}
+}
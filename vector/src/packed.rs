@@ -0,0 +1,214 @@
+use crate::*;
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+///A vector of `u32`s stored in `width` bits each, packed back to back into a `Vector<u64>`.
+/// Values may straddle two words.
+///
+/// The width grows to fit any value pushed or set, repacking every element when it does.
+pub struct PackedIntVector {
+	pub(crate) words: Vector<u64>,
+	pub(crate) len: usize,
+	pub(crate) width: u32,
+}
+
+impl Default for PackedIntVector {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl Debug for PackedIntVector {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list().entries(self.iter()).finish()
+	}
+}
+
+impl Clone for PackedIntVector {
+	fn clone(&self) -> Self {
+		PackedIntVector {
+			words: self.words.clone(),
+			len: self.len,
+			width: self.width,
+		}
+	}
+}
+
+impl PartialEq for PackedIntVector {
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+impl FromIterator<u32> for PackedIntVector {
+	fn from_iter<A: IntoIterator<Item = u32>>(iter: A) -> Self {
+		let mut vec = PackedIntVector::new();
+		for value in iter {
+			vec.push(value);
+		}
+		vec
+	}
+}
+
+///Returns the amount of bits needed to store `value`, at least 1.
+fn bits_needed(value: u32) -> u32 {
+	(u32::BITS - value.leading_zeros()).max(1)
+}
+
+impl PackedIntVector {
+	///Creates a new empty vector of 1 bit values. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Self::with_width(1)
+	}
+
+	///Creates a new empty vector storing values in `width` bits.
+	///
+	/// Panics if width isn't within 1 to 32.
+	pub fn with_width(width: u32) -> Self {
+		assert!(
+			(1..=u32::BITS).contains(&width),
+			"Width must be within 1 to 32 bits"
+		);
+		PackedIntVector {
+			words: Vector::new(),
+			len: 0,
+			width,
+		}
+	}
+
+	///Checks if the vector has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	///Returns the amount of elements stored in the vector.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	///Returns the amount of bits each element is stored in.
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	fn mask(&self) -> u64 {
+		(1 << self.width) - 1
+	}
+
+	//Reads the value at `idx` without bounds checking against the length.
+	fn read(&self, idx: usize) -> u32 {
+		let bit = idx * self.width as usize;
+		let (word, offset) = (bit / WORD_BITS, bit % WORD_BITS);
+		let mut value = self.words[word] >> offset;
+		if offset + self.width as usize > WORD_BITS {
+			value |= self.words[word + 1] << (WORD_BITS - offset);
+		}
+		(value & self.mask()) as u32
+	}
+
+	//Overwrites the value at `idx`, which must fit in the width.
+	fn write(&mut self, idx: usize, value: u32) {
+		let bit = idx * self.width as usize;
+		let (word, offset) = (bit / WORD_BITS, bit % WORD_BITS);
+		let (mask, value) = (self.mask(), value as u64);
+		self.words[word] = (self.words[word] & !(mask << offset)) | (value << offset);
+		if offset + self.width as usize > WORD_BITS {
+			let shift = WORD_BITS - offset;
+			self.words[word + 1] = (self.words[word + 1] & !(mask >> shift)) | (value >> shift);
+		}
+	}
+
+	///Repacks every element into `width` bits. Does nothing if the width is already at least
+	/// that large.
+	///
+	/// Panics if width is greater than 32. Has O(n) complexity.
+	pub fn widen(&mut self, width: u32) {
+		assert!(width <= u32::BITS, "Width must be within 1 to 32 bits");
+		if width <= self.width {
+			return;
+		}
+		let old = mem::replace(self, Self::with_width(width));
+		self.words = Vector::with_capacity((old.len * width as usize).div_ceil(WORD_BITS));
+		for value in old.iter() {
+			self.push(value);
+		}
+	}
+
+	///Inserts a value at the back of the vector, widening every element first if it doesn't fit.
+	///
+	/// Has complexity O(1), or O(n) if the width grows.
+	pub fn push(&mut self, value: u32) {
+		self.widen(bits_needed(value));
+		let words = ((self.len + 1) * self.width as usize).div_ceil(WORD_BITS);
+		while self.words.len() < words {
+			self.words.push(0);
+		}
+		self.len += 1;
+		self.write(self.len - 1, value);
+	}
+
+	///Removes the last value in the vector. The width is kept.
+	///
+	/// Returns `None` if the vector is empty. Has O(1) complexity.
+	pub fn pop(&mut self) -> Option<u32> {
+		if self.len == 0 {
+			return None;
+		}
+		let value = self.read(self.len - 1);
+		self.write(self.len - 1, 0);
+		self.len -= 1;
+		let words = (self.len * self.width as usize).div_ceil(WORD_BITS);
+		while self.words.len() > words {
+			self.words.pop();
+		}
+		Some(value)
+	}
+
+	///Gets the value at index's position.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has complexity O(1).
+	pub fn get(&self, idx: usize) -> Option<u32> {
+		if idx >= self.len {
+			return None;
+		}
+		Some(self.read(idx))
+	}
+
+	///Replaces the value at `idx`, widening every element first if it doesn't fit. Returns the
+	/// previous value.
+	///
+	/// Panics if idx is out of bounds. Has complexity O(1), or O(n) if the width grows.
+	pub fn set(&mut self, idx: usize, value: u32) -> u32 {
+		assert!(idx < self.len, "Index was out of bounds");
+		self.widen(bits_needed(value));
+		let old = self.read(idx);
+		self.write(idx, value);
+		old
+	}
+
+	///Removes every value. The width is kept.
+	///
+	/// Has O(n) complexity.
+	pub fn clear(&mut self) {
+		self.words.clear();
+		self.len = 0;
+	}
+
+	///Returns an iterator over the values of the vector.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> impl ExactSizeIterator<Item = u32> + '_ {
+		(0..self.len).map(move |idx| self.read(idx))
+	}
+
+	///Unpacks every value into a `Vector<u32>`.
+	///
+	/// Has O(n) complexity.
+	pub fn to_vector(&self) -> Vector<u32> {
+		let mut vec = Vector::with_capacity(self.len);
+		for value in self.iter() {
+			vec.push(value);
+		}
+		vec
+	}
+}
@@ -0,0 +1,233 @@
+use crate::*;
+use std::{num::NonZeroUsize, panic, thread};
+
+///Inputs shorter than this are processed on the calling thread, as spawning threads would cost
+/// more than it saves.
+pub(crate) const SEQUENTIAL_THRESHOLD: usize = 4096;
+
+fn available_threads() -> usize {
+	thread::available_parallelism().map_or(1, NonZeroUsize::get)
+}
+
+///Returns the length of the chunks to split `len` elements into, one per thread. Returns `len`
+/// itself if the work should stay on the calling thread.
+fn chunk_len(len: usize, threads: usize) -> usize {
+	if threads <= 1 || len < SEQUENTIAL_THRESHOLD {
+		len.max(1)
+	} else {
+		len.div_ceil(threads)
+	}
+}
+
+///Joins a worker, continuing its panic on the calling thread if it had one.
+fn join<R>(handle: thread::ScopedJoinHandle<'_, R>) -> R {
+	handle
+		.join()
+		.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+impl<T: Send, const ALIGN: usize> Vector<T, ALIGN> {
+	///Calls `f` on every element, splitting the vector into one chunk per available thread.
+	///
+	/// Runs on the calling thread for small vectors or if only one thread is available. Has O(n)
+	/// complexity.
+	pub fn par_for_each_mut<F>(&mut self, f: F)
+	where
+		F: Fn(&mut T) + Sync,
+	{
+		self.par_for_each_mut_on(available_threads(), f)
+	}
+
+	pub(crate) fn par_for_each_mut_on<F>(&mut self, threads: usize, f: F)
+	where
+		F: Fn(&mut T) + Sync,
+	{
+		let chunk = chunk_len(self.size, threads);
+		if chunk >= self.size {
+			self.iter_mut().for_each(f);
+			return;
+		}
+		let f = &f;
+		thread::scope(|s| {
+			for part in self.as_slice_mut().chunks_mut(chunk) {
+				s.spawn(move || part.iter_mut().for_each(f));
+			}
+		});
+	}
+
+	///Returns a new vector of `f` applied to every element, with each available thread mapping a
+	/// chunk of the vector.
+	///
+	/// Runs on the calling thread for small vectors or if only one thread is available. Has O(n)
+	/// complexity.
+	pub fn par_map<U, F>(&self, f: F) -> Vector<U>
+	where
+		T: Sync,
+		U: Send,
+		F: Fn(&T) -> U + Sync,
+	{
+		self.par_map_on(available_threads(), f)
+	}
+
+	pub(crate) fn par_map_on<U, F>(&self, threads: usize, f: F) -> Vector<U>
+	where
+		T: Sync,
+		U: Send,
+		F: Fn(&T) -> U + Sync,
+	{
+		let chunk = chunk_len(self.size, threads);
+		if chunk >= self.size {
+			return self.iter().map(f).collect();
+		}
+		let f = &f;
+		thread::scope(|s| {
+			let handles: Vector<_> = self
+				.as_slice()
+				.chunks(chunk)
+				.map(|part| s.spawn(move || part.iter().map(f).collect::<Vector<U>>()))
+				.collect();
+			let mut mapped = Vector::with_capacity(self.size);
+			for handle in handles {
+				for elem in join(handle) {
+					mapped.push(elem);
+				}
+			}
+			mapped
+		})
+	}
+
+	///Sorts the vector, keeping equal elements in their order. Each available thread sorts a
+	/// chunk, then neighbouring runs are merged pairwise in parallel, halving the amount of runs
+	/// every round. The last round merges two halves on a single thread.
+	///
+	/// Runs on the calling thread for small vectors or if only one thread is available. Has
+	/// O(n log n) complexity, and needs a buffer of up to half the vector while merging.
+	pub fn par_sort(&mut self)
+	where
+		T: Ord,
+	{
+		self.par_sort_on(available_threads())
+	}
+
+	pub(crate) fn par_sort_on(&mut self, threads: usize)
+	where
+		T: Ord,
+	{
+		//Zero sized elements are all equal, so they're already sorted. The merge couldn't measure
+		// its buffer in them either.
+		if mem::size_of::<T>() == 0 {
+			return;
+		}
+		let chunk = chunk_len(self.size, threads);
+		if chunk >= self.size {
+			self.as_slice_mut().sort();
+			return;
+		}
+		thread::scope(|s| {
+			for part in self.as_slice_mut().chunks_mut(chunk) {
+				s.spawn(move || part.sort());
+			}
+		});
+		let mut run = chunk;
+		while run < self.size {
+			thread::scope(|s| {
+				for pair in self.as_slice_mut().chunks_mut(run * 2) {
+					if pair.len() > run {
+						s.spawn(move || merge_runs(pair, run));
+					}
+				}
+			});
+			run *= 2;
+		}
+	}
+
+	///Combines every element with `op`, each available thread folding a chunk starting from
+	/// `identity()` before the chunk results are combined in order.
+	///
+	/// `op` must be associative and `identity()` must leave values unchanged when combined with
+	/// them, or the result depends on how the vector was split. Runs on the calling thread for
+	/// small vectors or if only one thread is available. Has O(n) complexity.
+	pub fn par_reduce<I, F>(&self, identity: I, op: F) -> T
+	where
+		T: Clone + Sync,
+		I: Fn() -> T + Sync,
+		F: Fn(T, T) -> T + Sync,
+	{
+		self.par_reduce_on(available_threads(), identity, op)
+	}
+
+	pub(crate) fn par_reduce_on<I, F>(&self, threads: usize, identity: I, op: F) -> T
+	where
+		T: Clone + Sync,
+		I: Fn() -> T + Sync,
+		F: Fn(T, T) -> T + Sync,
+	{
+		let chunk = chunk_len(self.size, threads);
+		if chunk >= self.size {
+			return self.iter().cloned().fold(identity(), op);
+		}
+		let (identity, op) = (&identity, &op);
+		thread::scope(|s| {
+			let handles: Vector<_> = self
+				.as_slice()
+				.chunks(chunk)
+				.map(|part| s.spawn(move || part.iter().cloned().fold(identity(), op)))
+				.collect();
+			handles.into_iter().map(join).fold(identity(), op)
+		})
+	}
+}
+
+///Merges the sorted runs `v[..mid]` and `v[mid..]` in place, keeping equal elements in order.
+/// The left run is moved out to a buffer and merged back from the front.
+fn merge_runs<T: Ord>(v: &mut [T], mid: usize) {
+	let mut buf: Vector<T> = Vector::with_capacity(mid);
+	let buf = buf.as_ptr_mut().expect("Vector was just allocated?");
+	let v_end = v.len();
+	let v = v.as_mut_ptr();
+	//Safety: The buffer has space for the left run, which stays in `hole` until merged. Every
+	// element is either before `hole.dest`, in the hole or from `right` on, so if a comparison
+	// panics the hole is moved back into the gap between `hole.dest` and `right`.
+	unsafe {
+		ptr::copy_nonoverlapping(v, buf, mid);
+		let mut hole = MergeHole {
+			start: buf,
+			end: buf.add(mid),
+			dest: v,
+		};
+		let mut right = v.add(mid);
+		let right_end = v.add(v_end);
+		while hole.start < hole.end && right < right_end {
+			//Only taken from the right if strictly smaller, so equal elements keep their order.
+			let src = if *right < *hole.start {
+				right = right.add(1);
+				right.sub(1)
+			} else {
+				hole.start = hole.start.add(1);
+				hole.start.sub(1)
+			};
+			//The destination may be the right element itself.
+			ptr::copy(src, hole.dest, 1);
+			hole.dest = hole.dest.add(1);
+		}
+		//Dropping the hole moves what's left of the left run in front of the rest of the right
+		// run, which is already in place.
+	}
+}
+
+///Elements of the left run not yet merged back, moved to `dest` when dropped.
+struct MergeHole<T> {
+	start: *mut T,
+	end: *mut T,
+	dest: *mut T,
+}
+
+impl<T> Drop for MergeHole<T> {
+	fn drop(&mut self) {
+		//Safety: The hole is in the buffer and the gap at `dest` is exactly as long.
+		unsafe {
+			let len = self.end.offset_from(self.start) as usize;
+			ptr::copy_nonoverlapping(self.start, self.dest, len);
+		}
+	}
+}
@@ -0,0 +1,225 @@
+use crate::*;
+use std::{
+	convert::TryFrom,
+	error::Error,
+	io::{self, Read, Write},
+};
+
+///Types that are plain bytes: `Copy`, without padding and valid for any bit pattern. These can be
+/// written to and read from files byte for byte with `Vector::write_to` and `Vector::read_from`.
+///
+/// # Safety
+/// Implementors must not contain padding, pointers or references and every bit pattern must be a
+/// valid value. `#[repr(C)]` structs of `Pod` fields without padding between them qualify.
+pub unsafe trait Pod: Copy + 'static {
+	///Stored in the file header to tell apart types of equal size and alignment, like `u32`
+	/// and `f32`. Types outside the standard library are all `PodKind::Other`.
+	const KIND: PodKind = PodKind::Other;
+}
+
+///The kind of number a `Pod` type holds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PodKind {
+	Other = 0,
+	Unsigned = 1,
+	Signed = 2,
+	Float = 3,
+}
+
+macro_rules! impl_pod {
+	($kind:ident: $($t:ty),+) => {
+		$(unsafe impl Pod for $t {
+			const KIND: PodKind = PodKind::$kind;
+		})+
+	};
+}
+
+impl_pod!(Unsigned: u8, u16, u32, u64, u128, usize);
+impl_pod!(Signed: i8, i16, i32, i64, i128, isize);
+impl_pod!(Float: f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {
+	const KIND: PodKind = T::KIND;
+}
+
+///Identifies a file as a vector dump, the last two bytes are the format version.
+const MAGIC: [u8; 8] = *b"VECPOD01";
+pub(crate) const HEADER_LEN: usize = 32;
+///Most bytes `Vector::read_from` reads, and allocates ahead of the data, at once.
+const READ_CHUNK_BYTES: usize = 1 << 16;
+
+const LITTLE_ENDIAN: u8 = 0;
+const BIG_ENDIAN: u8 = 1;
+const NATIVE_ENDIAN: u8 = if cfg!(target_endian = "little") {
+	LITTLE_ENDIAN
+} else {
+	BIG_ENDIAN
+};
+
+///Why a vector dump was rejected by `Vector::read_from`. Returned inside an `io::Error` of kind
+/// `InvalidData`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PodError {
+	///The file doesn't start with the expected magic bytes.
+	Magic,
+	///The file holds elements of a different size.
+	ElementSize { expected: usize, found: usize },
+	///The file holds elements with a different alignment.
+	Alignment { expected: usize, found: usize },
+	///The file holds a different kind of number.
+	Kind { expected: u8, found: u8 },
+	///The file was written on a machine with different endianness.
+	Endianness,
+	///The length in the header doesn't fit in memory.
+	Length,
+}
+
+impl fmt::Display for PodError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			PodError::Magic => write!(f, "Not a vector dump"),
+			PodError::ElementSize { expected, found } => write!(
+				f,
+				"Element size mismatch, expected {} found {}",
+				expected, found
+			),
+			PodError::Alignment { expected, found } => write!(
+				f,
+				"Element alignment mismatch, expected {} found {}",
+				expected, found
+			),
+			PodError::Kind { expected, found } => write!(
+				f,
+				"Element kind mismatch, expected {} found {}",
+				expected, found
+			),
+			PodError::Endianness => write!(f, "Vector dump has the wrong endianness"),
+			PodError::Length => write!(f, "Vector dump is too long to load"),
+		}
+	}
+}
+
+impl Error for PodError {}
+
+impl From<PodError> for io::Error {
+	fn from(error: PodError) -> Self {
+		io::Error::new(io::ErrorKind::InvalidData, error)
+	}
+}
+
+///Fills in a header describing `len` elements of `T` behind `magic`.
+///
+/// Layout: magic (8 bytes), element size (u32), alignment (u32), kind (u8), endianness (u8),
+/// six bytes of padding and the length (u64). Numbers are little endian.
+pub(crate) fn write_header<T: Pod>(header: &mut [u8; HEADER_LEN], magic: [u8; 8], len: u64) {
+	header[0..8].copy_from_slice(&magic);
+	header[8..12].copy_from_slice(&(mem::size_of::<T>() as u32).to_le_bytes());
+	header[12..16].copy_from_slice(&(mem::align_of::<T>() as u32).to_le_bytes());
+	header[16] = T::KIND as u8;
+	header[17] = NATIVE_ENDIAN;
+	header[24..32].copy_from_slice(&len.to_le_bytes());
+}
+
+///Checks that a header written by `write_header` describes elements of `T` on this machine and
+/// returns the length it holds.
+pub(crate) fn read_header<T: Pod>(
+	header: &[u8; HEADER_LEN],
+	magic: [u8; 8],
+) -> Result<u64, PodError> {
+	let u32_at = |i: usize| {
+		u32::from_le_bytes([header[i], header[i + 1], header[i + 2], header[i + 3]]) as usize
+	};
+
+	if header[0..8] != magic {
+		return Err(PodError::Magic);
+	}
+	let (size, align) = (u32_at(8), u32_at(12));
+	if size != mem::size_of::<T>() {
+		return Err(PodError::ElementSize {
+			expected: mem::size_of::<T>(),
+			found: size,
+		});
+	}
+	if align != mem::align_of::<T>() {
+		return Err(PodError::Alignment {
+			expected: mem::align_of::<T>(),
+			found: align,
+		});
+	}
+	if header[16] != T::KIND as u8 {
+		return Err(PodError::Kind {
+			expected: T::KIND as u8,
+			found: header[16],
+		});
+	}
+	if header[17] != NATIVE_ENDIAN {
+		return Err(PodError::Endianness);
+	}
+	let mut len_bytes = [0; 8];
+	len_bytes.copy_from_slice(&header[24..32]);
+	Ok(u64::from_le_bytes(len_bytes))
+}
+
+impl<T: Pod, const ALIGN: usize> Vector<T, ALIGN> {
+	///Writes the vector to `writer` as a 32 byte header followed by the raw bytes of every element.
+	///
+	/// The header holds magic bytes, the element size, alignment and kind, the endianness and the
+	/// length. Header fields are little endian while the elements are written as they are in memory.
+	/// Has O(n) complexity.
+	pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+		let mut header = [0; HEADER_LEN];
+		write_header::<T>(&mut header, MAGIC, self.size as u64);
+		writer.write_all(&header)?;
+
+		let slice = self.as_slice();
+		//Safety: `Pod` types have no padding, so every byte of the slice is initialised.
+		let bytes = unsafe {
+			std::slice::from_raw_parts(slice.as_ptr() as *const u8, mem::size_of_val(slice))
+		};
+		writer.write_all(bytes)
+	}
+
+	///Reads a vector written by `write_to` from `reader`, straight into the new vector's buffer.
+	///
+	/// Returns an `InvalidData` error holding a `PodError` if the header doesn't match `T` or
+	/// this machine's endianness, and `UnexpectedEof` if the data is shorter than the header
+	/// claims. The buffer grows as data is read, so a corrupt length can't allocate more than
+	/// the data holds. Has O(n) complexity.
+	pub fn read_from<R: Read>(reader: &mut R) -> io::Result<Self> {
+		let mut header = [0; HEADER_LEN];
+		reader.read_exact(&mut header)?;
+		let len =
+			usize::try_from(read_header::<T>(&header, MAGIC)?).map_err(|_| PodError::Length)?;
+		let size = mem::size_of::<T>();
+		len.checked_mul(size)
+			.filter(|&bytes| bytes <= isize::MAX as usize)
+			.ok_or(PodError::Length)?;
+
+		if size == 0 {
+			let mut vec = Vector::new_aligned();
+			//Safety: Zero sized types have nothing to read and infinite capacity.
+			unsafe { vec.set_len(len) };
+			return Ok(vec);
+		}
+
+		//The header isn't trusted with the allocation size, so the buffer grows as data arrives.
+		let chunk = (READ_CHUNK_BYTES / size).max(1);
+		let mut vec = Vector::with_capacity_aligned(len.min(chunk));
+		while vec.len() < len {
+			let count = (len - vec.len()).min(chunk);
+			vec.grow_for(count);
+			//Safety: The buffer has space for `count` more elements after the length. They're
+			// zeroed first so the slice never covers uninitialised memory, after which any bytes
+			// are a valid `T`.
+			unsafe {
+				let ptr = (vec.as_ptr_mut().expect("Vector was just allocated?") as *mut u8)
+					.add(vec.len() * size);
+				ptr::write_bytes(ptr, 0, count * size);
+				reader.read_exact(std::slice::from_raw_parts_mut(ptr, count * size))?;
+				vec.set_len(vec.len() + count);
+			}
+		}
+		Ok(vec)
+	}
+}
@@ -0,0 +1,186 @@
+use crate::*;
+use std::{
+	cell::{Cell, RefCell},
+	ops::{Deref, DerefMut},
+};
+
+///Vectors are bucketed by the power of two at or below their capacity, so every vector in bucket
+/// `k` holds at least `1 << k` elements.
+const CLASSES: usize = usize::BITS as usize;
+
+///A pool of empty vectors kept around with their capacity, to reuse allocations instead of
+/// freeing and allocating similarly sized buffers over and over.
+///
+/// The pool is meant to be used from one thread, for example kept in a `thread_local!`. Vectors
+/// are handed out as `PooledVector` guards which return them to the pool when dropped.
+///
+/// ```
+/// use vector::VectorPool;
+///
+/// thread_local! {
+///     static BUFFERS: VectorPool<u8> = VectorPool::new(1 << 20);
+/// }
+///
+/// BUFFERS.with(|pool| {
+///     let mut buf = pool.get(100);
+///     buf.push(1);
+///     assert!(buf.capacity() >= 100);
+/// });
+/// BUFFERS.with(|pool| assert_eq!(pool.len(), 1));
+/// ```
+pub struct VectorPool<T> {
+	pub(crate) buckets: RefCell<Vector<Vector<Vector<T>>>>,
+	pub(crate) retained: Cell<usize>,
+	pub(crate) max_retained: usize,
+}
+
+impl<T> Debug for VectorPool<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("VectorPool")
+			.field("len", &self.len())
+			.field("retained_bytes", &self.retained.get())
+			.field("max_retained_bytes", &self.max_retained)
+			.finish()
+	}
+}
+
+fn bytes<T>(vec: &Vector<T>) -> usize {
+	vec.capacity().saturating_mul(mem::size_of::<T>())
+}
+
+impl<T> VectorPool<T> {
+	///Creates an empty pool which keeps at most `max_retained_bytes` of returned buffers. Buffers
+	/// returned once it's full are freed.
+	pub fn new(max_retained_bytes: usize) -> Self {
+		let mut buckets = Vector::with_capacity(CLASSES);
+		for _ in 0..CLASSES {
+			buckets.push(Vector::new());
+		}
+		VectorPool {
+			buckets: RefCell::new(buckets),
+			retained: Cell::new(0),
+			max_retained: max_retained_bytes,
+		}
+	}
+
+	///Returns the amount of vectors waiting in the pool.
+	pub fn len(&self) -> usize {
+		self.buckets.borrow().iter().map(Vector::len).sum()
+	}
+
+	///Checks if the pool holds no vectors.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	///Returns the bytes allocated by the vectors waiting in the pool.
+	pub fn retained_bytes(&self) -> usize {
+		self.retained.get()
+	}
+
+	///Returns the most bytes the pool keeps.
+	pub fn max_retained_bytes(&self) -> usize {
+		self.max_retained
+	}
+
+	///Hands out an empty vector with space for at least `min_capacity` elements, reusing a pooled
+	/// one if any is large enough. New vectors get the next power of two as capacity so they
+	/// fit the same requests when returned.
+	///
+	/// Panics if the next power of two overflows. Has O(log n) complexity in the requested
+	/// capacity.
+	pub fn get(&self, min_capacity: usize) -> PooledVector<'_, T> {
+		if mem::size_of::<T>() == 0 {
+			return PooledVector {
+				vec: Some(Vector::new()),
+				pool: self,
+			};
+		}
+		let capacity = min_capacity
+			.max(1)
+			.checked_next_power_of_two()
+			.expect("Overflow");
+		let class = capacity.trailing_zeros() as usize;
+		let mut buckets = self.buckets.borrow_mut();
+		let reused = buckets
+			.iter_mut()
+			.skip(class)
+			.find_map(|bucket| bucket.pop());
+		let vec = match reused {
+			Some(vec) => {
+				self.retained.set(self.retained.get() - bytes(&vec));
+				vec
+			}
+			None => Vector::with_capacity(capacity),
+		};
+		PooledVector {
+			vec: Some(vec),
+			pool: self,
+		}
+	}
+
+	///Clears `vec` and keeps it for reuse, unless that would go over the retained byte limit.
+	///
+	/// Has O(n) complexity in the length of the vector.
+	pub fn put(&self, mut vec: Vector<T>) {
+		let size = bytes(&vec);
+		if size == 0 || self.retained.get() + size > self.max_retained {
+			return;
+		}
+		vec.clear();
+		let class = (usize::BITS - 1 - vec.capacity().leading_zeros()) as usize;
+		self.buckets.borrow_mut()[class].push(vec);
+		self.retained.set(self.retained.get() + size);
+	}
+
+	///Frees every pooled vector.
+	pub fn clear(&self) {
+		for bucket in self.buckets.borrow_mut().iter_mut() {
+			bucket.clear();
+		}
+		self.retained.set(0);
+	}
+}
+
+///A vector borrowed from a `VectorPool`, returned to it when dropped. Dereferences to the
+/// `Vector`.
+pub struct PooledVector<'a, T> {
+	//Only `None` while being dropped or detached.
+	pub(crate) vec: Option<Vector<T>>,
+	pub(crate) pool: &'a VectorPool<T>,
+}
+
+impl<'a, T> PooledVector<'a, T> {
+	///Takes the vector out of the pool's care, so it isn't returned on drop.
+	pub fn detach(mut self) -> Vector<T> {
+		self.vec.take().expect("Vector already taken?")
+	}
+}
+
+impl<'a, T: Debug> Debug for PooledVector<'a, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		Debug::fmt(&**self, f)
+	}
+}
+
+impl<'a, T> Deref for PooledVector<'a, T> {
+	type Target = Vector<T>;
+
+	fn deref(&self) -> &Self::Target {
+		self.vec.as_ref().expect("Vector already taken?")
+	}
+}
+
+impl<'a, T> DerefMut for PooledVector<'a, T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.vec.as_mut().expect("Vector already taken?")
+	}
+}
+
+impl<'a, T> Drop for PooledVector<'a, T> {
+	fn drop(&mut self) {
+		if let Some(vec) = self.vec.take() {
+			self.pool.put(vec);
+		}
+	}
+}
@@ -0,0 +1,193 @@
+///Declares a record struct together with a struct-of-arrays vector storing each of its fields
+/// in a separate `Vector`, plus borrowed views of a single record.
+///
+/// ```
+/// vector::soa_vector! {
+///     #[derive(Debug, Clone, PartialEq)]
+///     pub struct Record {
+///         pub id: u32,
+///         pub score: f64,
+///     }
+///     pub struct RecordVector, RecordRef, RecordMut, RecordSlicesMut;
+/// }
+///
+/// let mut records = RecordVector::new();
+/// records.push(Record { id: 1, score: 0.5 });
+/// records.push(Record { id: 2, score: 1.5 });
+/// assert_eq!(records.id(), &[1, 2]);
+/// assert_eq!(*records.get(1).unwrap().score, 1.5);
+/// ```
+///
+/// The second line names the generated types: the vector itself, a struct of references
+/// to one record's fields, the same with mutable references, and a struct of mutable slices
+/// over every column. Each field gets a method of the same name on the vector returning that
+/// column as a slice, so fields can't share a name with the vector's other methods.
+#[macro_export]
+macro_rules! soa_vector {
+	(
+		$(#[$attr:meta])*
+		$vis:vis struct $name:ident {
+			$($field_vis:vis $field:ident : $ty:ty),+ $(,)?
+		}
+		$vec_vis:vis struct $vec:ident, $ref_name:ident, $mut_name:ident, $slices_mut:ident;
+	) => {
+		$(#[$attr])*
+		$vis struct $name {
+			$($field_vis $field: $ty),+
+		}
+
+		///Struct-of-arrays storage where every field lives in its own `Vector`.
+		$vec_vis struct $vec {
+			$($field: $crate::Vector<$ty>),+
+		}
+
+		///References to the fields of one record in a struct-of-arrays vector.
+		#[derive(Clone, Copy)]
+		$vec_vis struct $ref_name<'a> {
+			$($field_vis $field: &'a $ty),+
+		}
+
+		///Mutable references to the fields of one record in a struct-of-arrays vector.
+		$vec_vis struct $mut_name<'a> {
+			$($field_vis $field: &'a mut $ty),+
+		}
+
+		///Every column of a struct-of-arrays vector as a mutable slice.
+		$vec_vis struct $slices_mut<'a> {
+			$($field_vis $field: &'a mut [$ty]),+
+		}
+
+		impl ::std::default::Default for $vec {
+			fn default() -> Self {
+				Self::new()
+			}
+		}
+
+		impl ::std::iter::FromIterator<$name> for $vec {
+			fn from_iter<A: ::std::iter::IntoIterator<Item = $name>>(iter: A) -> Self {
+				let iter = iter.into_iter();
+				let (min, _) = iter.size_hint();
+				let mut vec = Self::with_capacity(min);
+				for item in iter {
+					vec.push(item);
+				}
+				vec
+			}
+		}
+
+		#[allow(dead_code)]
+		impl $vec {
+			///Creates a new vector. Does not allocate till it's needed.
+			pub fn new() -> Self {
+				$vec {
+					$($field: $crate::Vector::new()),+
+				}
+			}
+
+			///Creates a new vector with preallocated space for `cap` records in every column.
+			pub fn with_capacity(cap: usize) -> Self {
+				$vec {
+					$($field: $crate::Vector::with_capacity(cap)),+
+				}
+			}
+
+			///Returns the amount of records stored in the vector.
+			pub fn len(&self) -> usize {
+				//Every column has the same length, any of them will do.
+				[$(self.$field.len()),+][0]
+			}
+
+			///Checks if the vector has no records in it.
+			pub fn is_empty(&self) -> bool {
+				self.len() == 0
+			}
+
+			///Inserts a record at the back of the vector, splitting it into its columns.
+			///
+			/// Has complexity O(1).
+			pub fn push(&mut self, elem: $name) {
+				$(self.$field.push(elem.$field);)+
+			}
+
+			///Removes the last record in the vector.
+			///
+			/// Returns `None` if the vector is empty. Has O(1) complexity.
+			pub fn pop(&mut self) -> Option<$name> {
+				if self.is_empty() {
+					return None;
+				}
+				Some($name {
+					$($field: self.$field.pop().expect("Columns are out of sync?")),+
+				})
+			}
+
+			///Removes the record at index, moving every record after it one step to the left.
+			///
+			/// Panics if index >= to the vector's length. Has O(n) complexity.
+			pub fn remove(&mut self, idx: usize) -> $name {
+				if idx >= self.len() {
+					panic!("Index was out of bounds!");
+				}
+				$name {
+					$($field: self.$field.remove(idx)),+
+				}
+			}
+
+			///Removes every record in the vector.
+			///
+			/// Has O(n) complexity.
+			pub fn clear(&mut self) {
+				$(self.$field.clear();)+
+			}
+
+			///Gets references to the fields of the record at index's position.
+			///
+			/// Returns `None` if index is greater than the length of the vector. Has complexity O(1).
+			pub fn get(&self, idx: usize) -> Option<$ref_name<'_>> {
+				if idx >= self.len() {
+					return None;
+				}
+				Some($ref_name {
+					$($field: self.$field.get(idx)?),+
+				})
+			}
+
+			///Gets mutable references to the fields of the record at index's position.
+			///
+			/// Returns `None` if index is greater than the length of the vector. Has complexity O(1).
+			pub fn get_mut(&mut self, idx: usize) -> Option<$mut_name<'_>> {
+				if idx >= self.len() {
+					return None;
+				}
+				Some($mut_name {
+					$($field: self.$field.get_mut(idx)?),+
+				})
+			}
+
+			///Borrows every column as a mutable slice at once.
+			///
+			/// Has complexity O(1).
+			pub fn as_slices_mut(&mut self) -> $slices_mut<'_> {
+				$slices_mut {
+					$($field: self.$field.as_slice_mut()),+
+				}
+			}
+
+			///Returns an iterator over references to the fields of each record.
+			///
+			/// Has complexity O(1).
+			pub fn iter(&self) -> impl DoubleEndedIterator<Item = $ref_name<'_>> {
+				(0..self.len()).map(move |idx| self.get(idx).expect("Index is within length"))
+			}
+
+			$(
+				///Borrows this field's column as a slice.
+				///
+				/// Has complexity O(1).
+				pub fn $field(&self) -> &[$ty] {
+					self.$field.as_slice()
+				}
+			)+
+		}
+	};
+}
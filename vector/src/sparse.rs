@@ -0,0 +1,237 @@
+use crate::*;
+use std::ops::{Add, Mul};
+
+///A vector of `len` logical elements that only stores those differing from `T::default()`, as
+/// index/value pairs sorted by index.
+///
+/// Lookups and changes binary search the indices. Suited to vectors which are mostly default,
+/// like feature vectors.
+pub struct SparseVector<T: Default + PartialEq> {
+	pub(crate) indices: Vector<usize>,
+	pub(crate) values: Vector<T>,
+	pub(crate) len: usize,
+	//Returned by reference for elements which aren't stored.
+	pub(crate) default: T,
+}
+
+impl<T: Default + PartialEq + Debug> Debug for SparseVector<T> {
+	///Prints the stored entries as `{index: value}` along with the logical length.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "SparseVector({}) ", self.len)?;
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<T: Default + PartialEq + Clone> Clone for SparseVector<T> {
+	fn clone(&self) -> Self {
+		SparseVector {
+			indices: self.indices.clone(),
+			values: self.values.clone(),
+			len: self.len,
+			default: T::default(),
+		}
+	}
+}
+
+impl<T: Default + PartialEq> PartialEq for SparseVector<T> {
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+impl<T: Default + PartialEq> Index<usize> for SparseVector<T> {
+	type Output = T;
+	fn index(&self, index: usize) -> &Self::Output {
+		self.get(index).expect("Index was out of bounds")
+	}
+}
+
+impl<T: Default + PartialEq> From<Vector<T>> for SparseVector<T> {
+	///Keeps only the elements differing from the default. Has O(n) complexity.
+	fn from(dense: Vector<T>) -> Self {
+		let mut vec = SparseVector::new(dense.len());
+		for (idx, elem) in dense.into_iter().enumerate() {
+			if elem != vec.default {
+				vec.indices.push(idx);
+				vec.values.push(elem);
+			}
+		}
+		vec
+	}
+}
+
+impl<T: Default + PartialEq> SparseVector<T> {
+	///Creates a vector of `len` default elements. Does not allocate till an element is set.
+	pub fn new(len: usize) -> Self {
+		SparseVector {
+			indices: Vector::new(),
+			values: Vector::new(),
+			len,
+			default: T::default(),
+		}
+	}
+
+	///Checks if the vector has a logical length of 0.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	///Returns the logical length, counting default elements.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	///Returns the amount of stored elements, which are those differing from the default.
+	pub fn stored_len(&self) -> usize {
+		self.indices.len()
+	}
+
+	fn find(&self, idx: usize) -> Result<usize, usize> {
+		self.indices.as_slice().binary_search(&idx)
+	}
+
+	///Gets a reference to the element at index's position, which is the default if it isn't
+	/// stored.
+	///
+	/// Returns `None` if index is greater than the length of the vector. Has O(log n) complexity
+	/// in the amount of stored elements.
+	pub fn get(&self, idx: usize) -> Option<&T> {
+		if idx >= self.len {
+			return None;
+		}
+		match self.find(idx) {
+			Ok(pos) => Some(&self.values[pos]),
+			Err(_) => Some(&self.default),
+		}
+	}
+
+	///Sets the element at `idx`, storing it only if it differs from the default. Returns the
+	/// previous element.
+	///
+	/// Panics if idx is out of bounds. Has O(n) complexity in the amount of stored elements if
+	/// an element is added or removed and O(log n) otherwise.
+	pub fn set(&mut self, idx: usize, elem: T) -> T {
+		assert!(idx < self.len, "Index was out of bounds");
+		let is_default = elem == self.default;
+		match self.find(idx) {
+			Ok(pos) if is_default => {
+				self.indices.remove(pos);
+				self.values.remove(pos)
+			}
+			Ok(pos) => mem::replace(&mut self.values[pos], elem),
+			Err(_) if is_default => elem,
+			Err(pos) => {
+				self.indices.insert(pos, idx);
+				self.values.insert(pos, elem);
+				T::default()
+			}
+		}
+	}
+
+	///Inserts an element at the back, growing the logical length by one.
+	///
+	/// Has complexity O(1).
+	pub fn push(&mut self, elem: T) {
+		if elem != self.default {
+			self.indices.push(self.len);
+			self.values.push(elem);
+		}
+		self.len += 1;
+	}
+
+	///Sets every element back to the default, keeping the length.
+	///
+	/// Has O(n) complexity in the amount of stored elements.
+	pub fn clear(&mut self) {
+		self.indices.clear();
+		self.values.clear();
+	}
+
+	///Returns an iterator over the stored elements and their indices, in index order.
+	///
+	/// Has complexity O(1).
+	pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> + '_ {
+		self.indices.iter().copied().zip(self.values.iter())
+	}
+
+	///Expands into a regular `Vector` with every default element filled in.
+	///
+	/// Has O(n) complexity in the logical length.
+	pub fn to_dense(&self) -> Vector<T>
+	where
+		T: Clone,
+	{
+		let mut dense = Vector::with_capacity(self.len);
+		let mut stored = self.iter().peekable();
+		for idx in 0..self.len {
+			match stored.peek() {
+				Some(&(next, elem)) if next == idx => {
+					dense.push(elem.clone());
+					stored.next();
+				}
+				_ => dense.push(T::default()),
+			}
+		}
+		dense
+	}
+
+	///Returns the sum of products of elements at the same index. Only indices stored in both
+	/// vectors contribute, found by merging the sorted indices.
+	///
+	/// Panics if the lengths differ. Has O(n + m) complexity in the amount of stored elements.
+	pub fn dot(&self, other: &Self) -> T
+	where
+		T: Copy + Add<Output = T> + Mul<Output = T>,
+	{
+		assert_eq!(self.len, other.len, "Vectors must be the same length");
+		let (a, b) = (self.indices.as_slice(), other.indices.as_slice());
+		let (mut i, mut j) = (0, 0);
+		let mut sum = T::default();
+		while i < a.len() && j < b.len() {
+			match a[i].cmp(&b[j]) {
+				std::cmp::Ordering::Less => i += 1,
+				std::cmp::Ordering::Greater => j += 1,
+				std::cmp::Ordering::Equal => {
+					sum = sum + self.values[i] * other.values[j];
+					i += 1;
+					j += 1;
+				}
+			}
+		}
+		sum
+	}
+
+	///Returns the elementwise sum, merging the sorted indices. Sums equal to the default are
+	/// left out.
+	///
+	/// Panics if the lengths differ. Has O(n + m) complexity in the amount of stored elements.
+	pub fn add(&self, other: &Self) -> Self
+	where
+		T: Clone + Add<Output = T>,
+	{
+		assert_eq!(self.len, other.len, "Vectors must be the same length");
+		let mut sum = SparseVector::new(self.len);
+		let mut push = |idx: usize, elem: T| {
+			if elem != sum.default {
+				sum.indices.push(idx);
+				sum.values.push(elem);
+			}
+		};
+		let (a, b) = (self.indices.as_slice(), other.indices.as_slice());
+		let (mut i, mut j) = (0, 0);
+		while i < a.len() || j < b.len() {
+			if j == b.len() || (i < a.len() && a[i] < b[j]) {
+				push(a[i], self.values[i].clone());
+				i += 1;
+			} else if i == a.len() || b[j] < a[i] {
+				push(b[j], other.values[j].clone());
+				j += 1;
+			} else {
+				push(a[i], self.values[i].clone() + other.values[j].clone());
+				i += 1;
+				j += 1;
+			}
+		}
+		sum
+	}
+}
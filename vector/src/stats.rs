@@ -0,0 +1,60 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+///Counters of the heap buffers allocated by every `Vector` in the process, as returned by
+/// `snapshot`. Only buffers of `Vector` itself are counted, not other containers' own
+/// allocations or the space taken by the elements' own heap data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+	///Buffers allocated for a vector which had none.
+	pub allocations: usize,
+	///Buffers allocated to replace a vector's existing one.
+	pub reallocations: usize,
+	///Buffers freed, when replaced or when their vector or iterator was dropped.
+	pub deallocations: usize,
+	///Bytes currently allocated.
+	pub live_bytes: usize,
+	///Most bytes allocated at once since the last `reset`.
+	pub peak_bytes: usize,
+}
+
+///Returns the current counters. Counters are updated independently, so a snapshot taken while
+/// other threads allocate may be slightly inconsistent.
+pub fn snapshot() -> AllocStats {
+	AllocStats {
+		allocations: ALLOCATIONS.load(Ordering::Relaxed),
+		reallocations: REALLOCATIONS.load(Ordering::Relaxed),
+		deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+		live_bytes: LIVE_BYTES.load(Ordering::Relaxed),
+		peak_bytes: PEAK_BYTES.load(Ordering::Relaxed),
+	}
+}
+
+///Zeroes the event counters and lowers the peak to the bytes currently live. The live bytes are
+/// kept, as the buffers they count are still allocated.
+pub fn reset() {
+	ALLOCATIONS.store(0, Ordering::Relaxed);
+	REALLOCATIONS.store(0, Ordering::Relaxed);
+	DEALLOCATIONS.store(0, Ordering::Relaxed);
+	PEAK_BYTES.store(LIVE_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+}
+
+pub(crate) fn record_alloc(bytes: usize, replaces: bool) {
+	if replaces {
+		REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+	} else {
+		ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+	}
+	let live = LIVE_BYTES.fetch_add(bytes, Ordering::Relaxed) + bytes;
+	PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+}
+
+pub(crate) fn record_dealloc(bytes: usize) {
+	DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+	LIVE_BYTES.fetch_sub(bytes, Ordering::Relaxed);
+}
@@ -0,0 +1,81 @@
+use crate::*;
+
+fn is_aligned<T, const ALIGN: usize>(vec: &AlignedVector<T, ALIGN>) -> bool {
+	(vec.as_ptr().expect("Vector has no buffer") as usize).is_multiple_of(ALIGN)
+}
+
+#[test]
+fn push_realloc() {
+	let mut vec = AlignedVector::<u8, 64>::new_aligned();
+	for i in 0..1000 {
+		vec.push(i as u8);
+		assert!(is_aligned(&vec));
+	}
+	assert_eq!(vec[999], (999 % 256) as u8);
+}
+
+#[test]
+fn with_capacity() {
+	let mut vec = AlignedVector::<f32, 32>::with_capacity_aligned(7);
+	assert!(is_aligned(&vec));
+	for i in 0..20 {
+		vec.insert(0, i as f32);
+		assert!(is_aligned(&vec));
+	}
+	vec.remove(3);
+	vec.reserve_additional(100);
+	assert!(is_aligned(&vec));
+	assert_eq!(vec.len(), 19);
+}
+
+#[test]
+fn collect() {
+	let vec: AlignedVector<u16, 4096> = (0..100).collect();
+	assert!(is_aligned(&vec));
+	assert_eq!(vec.as_slice()[..3], [0, 1, 2]);
+}
+
+#[test]
+fn smaller_than_type() {
+	let mut vec = AlignedVector::<u64, 1>::new_aligned();
+	vec.push(1);
+	assert!((vec.as_ptr().unwrap() as usize).is_multiple_of(mem::align_of::<u64>()));
+}
+
+#[test]
+fn into_iter() {
+	let vec: AlignedVector<Box<i32>, 64> = (0..10).map(Box::new).collect();
+	let mut iter = vec.into_iter();
+	assert_eq!(iter.next(), Some(Box::new(0)));
+	assert_eq!(iter.next_back(), Some(Box::new(9)));
+	//The rest is dropped and the buffer freed with the aligned layout.
+}
+
+#[test]
+fn drop() {
+	static mut SUM: i32 = 0;
+	struct ToDrop;
+	impl Drop for ToDrop {
+		fn drop(&mut self) {
+			unsafe {
+				SUM += 1;
+			}
+		}
+	}
+	{
+		let mut vec = AlignedVector::<_, 32>::new_aligned();
+		for _ in 0..10 {
+			vec.push(ToDrop);
+		}
+		let mut iter = vec.into_iter();
+		iter.next();
+	}
+	assert_eq!(unsafe { SUM }, 10);
+}
+
+#[test]
+#[should_panic]
+fn not_power_of_two() {
+	let mut vec = AlignedVector::<u8, 48>::new_aligned();
+	vec.push(1);
+}
@@ -124,7 +124,7 @@ fn borrowed_iterator() {
 	vec.push(Box::new(3));
 	vec.push(Box::new(4));
 	vec.push(Box::new(5));
-	let std_vec = vec![
+	let std_vec = [
 		Box::new(1),
 		Box::new(2),
 		Box::new(3),
@@ -146,7 +146,7 @@ fn borrowed_back_iterator() {
 	vec.push(Box::new(3));
 	vec.push(Box::new(4));
 	vec.push(Box::new(5));
-	let std_vec = vec![
+	let std_vec = [
 		Box::new(1),
 		Box::new(2),
 		Box::new(3),
@@ -178,6 +178,7 @@ fn borrowed_both_iterator() {
 fn drop() {
 	static mut SUM: i32 = 0;
 	#[derive(Clone, Debug)]
+	#[allow(dead_code)]
 	struct ToDrop {
 		b: u8,
 	}
@@ -0,0 +1,107 @@
+use crate::*;
+use std::{
+	rc::Rc,
+	sync::atomic::{AtomicUsize, Ordering},
+	thread,
+};
+
+const THREADS: usize = 8;
+const PER_THREAD: usize = 10_000;
+
+#[test]
+fn push_get() {
+	let vec = ConcurrentVector::new();
+	assert!(vec.is_empty());
+	for i in 0..100 {
+		assert_eq!(vec.push(i), i);
+	}
+	assert_eq!(vec.len(), 100);
+	for i in 0..100 {
+		assert_eq!(vec[i], i);
+	}
+	assert_eq!(vec.get(100), None);
+	assert!(vec.iter().copied().eq(0..100));
+}
+
+#[test]
+fn stress_push() {
+	let vec = ConcurrentVector::new();
+	thread::scope(|s| {
+		for t in 0..THREADS {
+			let vec = &vec;
+			s.spawn(move || {
+				for i in 0..PER_THREAD {
+					let idx = vec.push(t * PER_THREAD + i);
+					assert_eq!(vec[idx], t * PER_THREAD + i);
+				}
+			});
+		}
+	});
+	assert_eq!(vec.len(), THREADS * PER_THREAD);
+	let mut seen = Vector::new();
+	for _ in 0..THREADS * PER_THREAD {
+		seen.push(false);
+	}
+	for &elem in vec.iter() {
+		assert!(!seen[elem], "{} pushed twice", elem);
+		seen[elem] = true;
+	}
+	assert!(seen.iter().all(|&seen| seen));
+}
+
+#[test]
+fn stress_read_while_pushing() {
+	let vec = ConcurrentVector::new();
+	let done = AtomicUsize::new(0);
+	thread::scope(|s| {
+		for t in 0..THREADS {
+			let (vec, done) = (&vec, &done);
+			s.spawn(move || {
+				for i in 0..PER_THREAD {
+					vec.push((t, i, Box::new(t ^ i)));
+				}
+				done.fetch_add(1, Ordering::Release);
+			});
+		}
+		//Every element a reader gets must be fully written.
+		for _ in 0..2 {
+			let (vec, done) = (&vec, &done);
+			s.spawn(move || {
+				while done.load(Ordering::Acquire) != THREADS {
+					let len = vec.len();
+					for idx in (0..len).step_by(97) {
+						if let Some((t, i, boxed)) = vec.get(idx) {
+							assert_eq!(**boxed, t ^ i);
+						}
+					}
+					let prefix = vec.iter().count();
+					assert!(prefix <= vec.len());
+				}
+			});
+		}
+	});
+	assert_eq!(vec.iter().count(), THREADS * PER_THREAD);
+}
+
+#[test]
+fn drops_elements() {
+	let counter = Rc::new(());
+	{
+		let vec = ConcurrentVector::new();
+		for _ in 0..1000 {
+			vec.push(Rc::clone(&counter));
+		}
+		assert_eq!(Rc::strong_count(&counter), 1001);
+	}
+	assert_eq!(Rc::strong_count(&counter), 1);
+}
+
+#[test]
+fn into_vector() {
+	let vec: ConcurrentVector<Box<usize>> = (0..500).map(Box::new).collect();
+	let vec = vec.into_vector();
+	assert_eq!(vec.len(), 500);
+	for (i, elem) in vec.iter().enumerate() {
+		assert_eq!(**elem, i);
+	}
+}
@@ -0,0 +1,78 @@
+use crate::*;
+use std::thread;
+
+#[test]
+fn clone_shares() {
+	let a: CowVector<i32> = (0..5).collect();
+	let b = a.clone();
+	assert!(CowVector::ptr_eq(&a, &b));
+	assert!(a.is_shared());
+	assert_eq!(&b[..], &[0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn make_mut_copies_shared() {
+	let mut a: CowVector<i32> = (0..5).collect();
+	let b = a.clone();
+	a.make_mut().push(5);
+	assert!(!CowVector::ptr_eq(&a, &b));
+	assert_eq!(&a[..], &[0, 1, 2, 3, 4, 5]);
+	assert_eq!(&b[..], &[0, 1, 2, 3, 4]);
+	assert!(!a.is_shared());
+	assert!(!b.is_shared());
+}
+
+#[test]
+fn make_mut_unique() {
+	let mut a: CowVector<i32> = (0..5).collect();
+	let ptr = a.as_vector().as_ptr();
+	a.make_mut()[0] = 10;
+	assert_eq!(a.as_vector().as_ptr(), ptr);
+	assert_eq!(a[0], 10);
+}
+
+#[test]
+fn get_mut() {
+	let mut a: CowVector<i32> = (0..2).collect();
+	let b = a.clone();
+	assert!(a.get_mut().is_none());
+	drop(b);
+	a.get_mut().unwrap().push(2);
+	assert_eq!(a.len(), 3);
+}
+
+#[test]
+fn into_vector() {
+	let a: CowVector<Box<i32>> = (0..3).map(Box::new).collect();
+	let b = a.clone();
+	let vec = a.into_vector();
+	assert_eq!(*vec[2], 2);
+	assert!(!b.is_shared());
+	let vec: Vector<_> = b.into();
+	assert_eq!(vec.len(), 3);
+}
+
+#[test]
+fn arc_threads() {
+	let a: ArcCowVector<u64> = (0..1000).collect();
+	let handles: Vector<_> = (0..4)
+		.map(|_| {
+			let a = a.clone();
+			thread::spawn(move || a.iter().sum::<u64>())
+		})
+		.collect();
+	for handle in handles {
+		assert_eq!(handle.join().unwrap(), 499500);
+	}
+	let mut a = a;
+	a.make_mut().push(1);
+	assert_eq!(a.len(), 1001);
+}
+
+#[test]
+fn clone_vector() {
+	let vec: Vector<Box<i32>> = (0..3).map(Box::new).collect();
+	let copy = vec.clone();
+	assert_eq!(vec.as_slice(), copy.as_slice());
+	assert_ne!(vec.as_ptr(), copy.as_ptr());
+}
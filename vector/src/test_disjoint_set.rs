@@ -0,0 +1,70 @@
+use crate::*;
+
+#[test]
+fn union_find() {
+	let mut set = DisjointSet::with_len(6);
+	assert_eq!(set.set_count(), 6);
+	assert!(set.union(0, 1));
+	assert!(set.union(2, 3));
+	assert!(set.union(1, 3));
+	assert!(!set.union(0, 2));
+	assert_eq!(set.set_count(), 3);
+	assert!(set.same_set(0, 3));
+	assert!(!set.same_set(0, 4));
+	assert_eq!(set.set_size(2), 4);
+	assert_eq!(set.set_size(5), 1);
+	assert_eq!(set.find(1), set.find(2));
+}
+
+#[test]
+fn growth() {
+	let mut set = DisjointSet::new();
+	assert!(set.is_empty());
+	assert_eq!(set.make_set(), 0);
+	assert_eq!(set.make_set(), 1);
+	set.union(0, 1);
+	set.grow(4);
+	set.grow(2);
+	assert_eq!(set.len(), 4);
+	assert_eq!(set.set_count(), 3);
+	assert_eq!(set.make_set(), 4);
+	set.union(4, 0);
+	assert_eq!(set.set_size(1), 3);
+}
+
+#[test]
+fn sets() {
+	let mut set = DisjointSet::with_len(7);
+	set.union(5, 1);
+	set.union(6, 3);
+	set.union(3, 1);
+	let sets = set.sets();
+	let sets: Vector<&[usize]> = sets.iter().map(Vector::as_slice).collect();
+	assert_eq!(sets.as_slice(), &[&[0][..], &[1, 3, 5, 6], &[2], &[4]]);
+}
+
+#[test]
+fn path_compression() {
+	//Merging equally sized sets over and over builds the tallest trees union by rank allows.
+	let mut set = DisjointSet::with_len(1 << 12);
+	let mut width = 1;
+	while width < set.len() {
+		for start in (0..set.len()).step_by(width * 2) {
+			set.union(start, start + width);
+		}
+		width *= 2;
+	}
+	assert_eq!(set.set_count(), 1);
+	let root = set.find(set.len() - 1);
+	for elem in 0..set.len() {
+		assert_eq!(set.find(elem), root);
+		assert_eq!(set.parents[elem], root);
+	}
+	assert!(set.ranks[root] <= 12);
+}
+
+#[test]
+#[should_panic(expected = "Index was out of bounds")]
+fn out_of_bounds() {
+	DisjointSet::with_len(3).find(3);
+}
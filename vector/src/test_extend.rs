@@ -0,0 +1,77 @@
+use crate::*;
+use std::{
+	cell::Cell,
+	panic::{catch_unwind, AssertUnwindSafe},
+	rc::Rc,
+};
+
+#[test]
+fn macro_forms() {
+	let list = vector![1, 2, 3,];
+	assert_eq!(list.as_slice(), &[1, 2, 3]);
+	assert_eq!(list.capacity(), 3);
+	let boxes = vector![Box::new(1); 3];
+	assert_eq!(boxes.len(), 3);
+	assert!(boxes.iter().all(|b| **b == 1));
+	assert!(vector![String::new(); 0].is_empty());
+	let empty: Vector<u8> = vector![];
+	assert!(empty.is_empty());
+	let zst = vector![(); 10];
+	assert_eq!(zst.len(), 10);
+}
+
+#[test]
+fn extend_reserves_once() {
+	let mut vec: Vector<u32> = vector![0];
+	vec.extend(1..100);
+	assert!(vec.iter().copied().eq(0..100));
+	assert_eq!(vec.capacity(), 100);
+
+	//A filter's lower bound is 0, so everything is pushed.
+	vec.extend((100..200).filter(|x| x % 2 == 0));
+	assert_eq!(vec.len(), 150);
+	assert_eq!(vec[149], 198);
+
+	vec.extend(&[7, 8]);
+	assert_eq!(&vec.as_slice()[150..], &[7, 8]);
+}
+
+#[test]
+fn extend_from_slice_clones() {
+	let mut vec: Vector<String> = Vector::new();
+	vec.extend_from_slice(&["a".to_string(), "b".to_string()]);
+	vec.extend_from_slice(&[]);
+	vec.extend_from_slice(&["c".to_string()]);
+	assert_eq!(vec.as_slice(), &["a", "b", "c"]);
+
+	let mut zst: Vector<()> = Vector::new();
+	zst.extend_from_slice(&[(), ()]);
+	assert_eq!(zst.len(), 2);
+}
+
+//Panics when cloned once the shared counter runs out.
+struct Fuse(Rc<Cell<usize>>);
+
+impl Clone for Fuse {
+	fn clone(&self) -> Self {
+		let left = self.0.get();
+		assert!(left > 0, "Clone panicked");
+		self.0.set(left - 1);
+		Fuse(Rc::clone(&self.0))
+	}
+}
+
+#[test]
+fn extend_from_slice_panic_keeps_clones() {
+	let fuse = Rc::new(Cell::new(2));
+	let source: Vector<Fuse> = (0..5).map(|_| Fuse(Rc::clone(&fuse))).collect();
+	let mut vec = Vector::new();
+	let result = catch_unwind(AssertUnwindSafe(|| {
+		vec.extend_from_slice(source.as_slice())
+	}));
+	assert!(result.is_err());
+	assert_eq!(vec.len(), 2);
+	drop(vec);
+	//Only the source and the counter itself are left.
+	assert_eq!(Rc::strong_count(&fuse), 6);
+}
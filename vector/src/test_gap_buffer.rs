@@ -0,0 +1,125 @@
+use crate::*;
+
+fn text(buf: &GapBuffer<char>) -> String {
+	buf.iter().collect()
+}
+
+#[test]
+fn insert() {
+	let mut buf = GapBuffer::new();
+	for c in "hello".chars() {
+		buf.insert(c);
+	}
+	assert_eq!(buf.cursor(), 5);
+	buf.move_cursor(0);
+	buf.insert('>');
+	assert_eq!(text(&buf), ">hello");
+	assert_eq!(buf.len(), 6);
+}
+
+#[test]
+fn move_cursor() {
+	let mut buf: GapBuffer<char> = "abcdef".chars().collect();
+	buf.move_cursor(2);
+	assert_eq!(
+		buf.as_slices(),
+		(&['a', 'b'][..], &['c', 'd', 'e', 'f'][..])
+	);
+	buf.move_cursor(5);
+	assert_eq!(
+		buf.as_slices(),
+		(&['a', 'b', 'c', 'd', 'e'][..], &['f'][..])
+	);
+	buf.insert('x');
+	assert_eq!(text(&buf), "abcdexf");
+}
+
+#[test]
+#[should_panic]
+fn move_cursor_out_of_bounds() {
+	let mut buf: GapBuffer<char> = "abc".chars().collect();
+	buf.move_cursor(4);
+}
+
+#[test]
+fn delete() {
+	let mut buf: GapBuffer<char> = "abcdef".chars().collect();
+	buf.move_cursor(3);
+	assert_eq!(buf.delete(), Some('d'));
+	assert_eq!(buf.backspace(), Some('c'));
+	assert_eq!(text(&buf), "abef");
+	buf.move_cursor(0);
+	assert_eq!(buf.backspace(), None);
+	buf.move_cursor(4);
+	assert_eq!(buf.delete(), None);
+}
+
+#[test]
+fn index() {
+	let mut buf: GapBuffer<i32> = (0..10).collect();
+	buf.move_cursor(4);
+	for i in 0..10 {
+		assert_eq!(buf[i], i as i32);
+	}
+	buf[7] = 70;
+	assert_eq!(buf.get(7), Some(&70));
+	assert_eq!(buf.get(10), None);
+}
+
+#[test]
+fn vector_round_trip() {
+	let mut vec = Vector::new();
+	for i in 0..5 {
+		vec.push(Box::new(i));
+	}
+	let mut buf = GapBuffer::from(vec);
+	buf.move_cursor(1);
+	buf.insert(Box::new(10));
+	buf.move_cursor(3);
+	let vec = Vector::from(buf);
+	let vals: Vector<i32> = vec.into_iter().map(|b| *b).collect();
+	assert_eq!(vals.as_slice(), &[0, 10, 1, 2, 3, 4]);
+}
+
+#[test]
+fn make_contiguous() {
+	let mut buf: GapBuffer<i32> = (0..5).collect();
+	buf.move_cursor(2);
+	assert_eq!(buf.make_contiguous(), &[0, 1, 2, 3, 4]);
+	assert_eq!(buf.cursor(), 5);
+}
+
+#[test]
+fn zst() {
+	let mut buf = GapBuffer::new();
+	for _ in 0..10 {
+		buf.insert(());
+	}
+	buf.move_cursor(4);
+	assert_eq!(buf.delete(), Some(()));
+	assert_eq!(buf.len(), 9);
+	assert_eq!(Vector::from(buf).len(), 9);
+}
+
+#[test]
+fn drop() {
+	static mut SUM: i32 = 0;
+	struct ToDrop;
+	impl Drop for ToDrop {
+		fn drop(&mut self) {
+			unsafe {
+				SUM += 1;
+			}
+		}
+	}
+	{
+		let mut buf = GapBuffer::new();
+		for _ in 0..10 {
+			buf.insert(ToDrop);
+		}
+		buf.move_cursor(3);
+		buf.delete();
+		assert_eq!(unsafe { SUM }, 1);
+	}
+	assert_eq!(unsafe { SUM }, 10);
+}
@@ -103,7 +103,7 @@ fn borrowed_iterator() {
 	vec.push(3);
 	vec.push(4);
 	vec.push(5);
-	let std_vec = vec![1, 2, 3, 4, 5];
+	let std_vec = [1, 2, 3, 4, 5];
 	let mut std_iter = std_vec.iter();
 	let mut iter = vec.iter();
 	for _ in 0..6 {
@@ -119,7 +119,7 @@ fn borrowed_back_iterator() {
 	vec.push(3);
 	vec.push(4);
 	vec.push(5);
-	let std_vec = vec![1, 2, 3, 4, 5];
+	let std_vec = [1, 2, 3, 4, 5];
 	let mut std_iter = std_vec.iter().rev();
 	let mut iter = vec.iter().rev();
 	for _ in 0..6 {
@@ -145,6 +145,7 @@ fn borrowed_both_iterator() {
 fn drop() {
 	static mut SUM: i32 = 0;
 	#[derive(Clone, Debug)]
+	#[allow(dead_code)]
 	struct ToDrop {
 		b: u8,
 	}
@@ -0,0 +1,49 @@
+use crate::*;
+
+index_newtype! {
+	struct NodeId;
+	///Documented index.
+	struct EdgeId;
+}
+
+#[test]
+fn push_returns_index() {
+	let mut nodes: IndexVector<NodeId, &str> = IndexVector::new();
+	assert_eq!(nodes.next_index(), NodeId(0));
+	let a = nodes.push("a");
+	let b = nodes.push("b");
+	assert_eq!((a, b), (NodeId(0), NodeId(1)));
+	assert_eq!(nodes[a], "a");
+	nodes[b] = "c";
+	assert_eq!(nodes.get(b), Some(&"c"));
+	assert_eq!(nodes.get(NodeId(2)), None);
+	assert_eq!(nodes.pop(), Some("c"));
+	assert_eq!(nodes.len(), 1);
+}
+
+#[test]
+fn iter_enumerated() {
+	let mut edges: IndexVector<EdgeId, i32> = (10..14).collect();
+	let pairs: Vector<(EdgeId, i32)> = edges.iter_enumerated().map(|(i, &x)| (i, x)).collect();
+	assert_eq!(
+		pairs.as_slice(),
+		&[
+			(EdgeId(0), 10),
+			(EdgeId(1), 11),
+			(EdgeId(2), 12),
+			(EdgeId(3), 13)
+		]
+	);
+	for (i, elem) in edges.iter_enumerated_mut().rev() {
+		*elem += i.index() as i32;
+	}
+	assert_eq!(edges.as_vector().as_slice(), &[10, 12, 14, 16]);
+	assert!(edges.indices().eq((0..4).map(EdgeId)));
+}
+
+#[test]
+fn usize_index() {
+	let vec: IndexVector<usize, i32> = Vector::from_iter(0..3).into();
+	assert_eq!(vec[2], 2);
+	assert_eq!(Vector::from(vec).len(), 3);
+}
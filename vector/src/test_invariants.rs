@@ -0,0 +1,65 @@
+use crate::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+#[test]
+fn mutations_keep_invariants() {
+	let mut vec: Vector<u16, 64> = Vector::new_aligned();
+	for i in 0..100 {
+		vec.push(i);
+	}
+	vec.insert(3, 7);
+	vec.remove(50);
+	vec.pop();
+	vec.retain(|x| x % 3 != 0);
+	vec.extend_from_slice(&[1, 2, 3]);
+	vec.reserve_additional(10);
+	vec.check_invariants();
+	vec.clear();
+	vec.check_invariants();
+
+	let mut zst: Vector<()> = Vector::new();
+	zst.push(());
+	zst.check_invariants();
+}
+
+#[test]
+fn detects_write_past_end() {
+	let mut vec: Vector<u32> = Vector::with_capacity(4);
+	vec.extend_from_slice(&[1, 2, 3, 4]);
+	let end = unsafe { vec.as_ptr_mut().unwrap().add(4) as *mut u8 };
+	//Safety: The canary directly follows the buffer.
+	let canary = unsafe { end.read() };
+	unsafe { end.write(!canary) };
+	let result = catch_unwind(AssertUnwindSafe(|| vec.check_invariants()));
+	assert!(result.is_err());
+	//Restored so the vector can be freed.
+	unsafe { end.write(canary) };
+	vec.check_invariants();
+}
+
+#[test]
+fn detects_write_before_start() {
+	let mut vec: Vector<u64> = Vector::with_capacity(2);
+	vec.push(1);
+	let start = unsafe { (vec.as_ptr_mut().unwrap() as *mut u8).sub(1) };
+	//Safety: The canary directly precedes the buffer.
+	let canary = unsafe { start.read() };
+	unsafe { start.write(!canary) };
+	assert!(catch_unwind(AssertUnwindSafe(|| vec.push(2))).is_err());
+	unsafe { start.write(canary) };
+	vec.check_invariants();
+}
+
+#[test]
+fn detects_broken_length_and_capacity() {
+	let mut vec: Vector<u8> = Vector::with_capacity(2);
+	vec.size = 3;
+	assert!(catch_unwind(AssertUnwindSafe(|| vec.check_invariants())).is_err());
+	vec.size = 0;
+
+	let mut empty: Vector<u8> = Vector::new();
+	empty.capacity = 8;
+	assert!(catch_unwind(AssertUnwindSafe(|| empty.check_invariants())).is_err());
+	//Restored as dropping checks the invariants too.
+	empty.capacity = 0;
+}
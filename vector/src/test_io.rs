@@ -0,0 +1,69 @@
+use crate::*;
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+#[test]
+fn extend_from_slice() {
+	let mut vec = Vector::new();
+	vec.extend_from_slice(&[1, 2, 3]);
+	vec.extend_from_slice(&[]);
+	vec.push(4);
+	vec.extend_from_slice(&[5, 6, 7, 8, 9, 10]);
+	assert_eq!(vec.as_slice(), &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+}
+
+#[test]
+fn extend_from_slice_zst() {
+	let mut vec = Vector::new();
+	vec.extend_from_slice(&[(), (), ()]);
+	assert_eq!(vec.len(), 3);
+}
+
+#[test]
+fn write() {
+	let mut vec = Vector::new();
+	vec.write_all(b"frame").unwrap();
+	write!(vec, ":{}", 42).unwrap();
+	vec.flush().unwrap();
+	assert_eq!(vec.as_slice(), b"frame:42");
+}
+
+#[test]
+fn read() {
+	let mut vec = Vector::new();
+	vec.extend_from_slice(b"hello world");
+	let mut cursor = VectorCursor::new(vec);
+	let mut buf = [0; 5];
+	cursor.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"hello");
+	let mut rest = String::new();
+	cursor.read_to_string(&mut rest).unwrap();
+	assert_eq!(rest, " world");
+	assert_eq!(cursor.read(&mut buf).unwrap(), 0);
+	assert!(cursor.read_exact(&mut buf).is_err());
+}
+
+#[test]
+fn buf_read() {
+	let mut vec = Vector::new();
+	vec.extend_from_slice(b"one\ntwo\nthree");
+	let cursor = VectorCursor::new(vec);
+	let lines: Vector<String> = cursor.lines().map(|l| l.unwrap()).collect();
+	assert_eq!(lines.as_slice(), &["one", "two", "three"]);
+}
+
+#[test]
+fn seek() {
+	let mut vec = Vector::new();
+	vec.extend_from_slice(b"0123456789");
+	let mut cursor = VectorCursor::new(vec);
+	let mut buf = [0; 2];
+	assert_eq!(cursor.seek(SeekFrom::End(-3)).unwrap(), 7);
+	cursor.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"78");
+	assert_eq!(cursor.seek(SeekFrom::Current(-5)).unwrap(), 4);
+	cursor.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"45");
+	assert!(cursor.seek(SeekFrom::Current(-10)).is_err());
+	assert_eq!(cursor.seek(SeekFrom::Start(20)).unwrap(), 20);
+	assert_eq!(cursor.read(&mut buf).unwrap(), 0);
+}
@@ -0,0 +1,78 @@
+use crate::*;
+
+#[test]
+fn push_rows() {
+	let mut vec = JaggedVector::new();
+	assert!(vec.is_empty());
+	assert_eq!(vec.push_row(0..3), 0);
+	assert_eq!(vec.push_row(None), 1);
+	assert_eq!(vec.push_row(vec![7]), 2);
+	assert_eq!(vec.len(), 3);
+	assert_eq!(vec.items_len(), 4);
+	assert_eq!(vec.row(0), &[0, 1, 2]);
+	assert!(vec.row(1).is_empty());
+	assert_eq!(&vec[2], &[7]);
+	assert_eq!(vec.get(3), None);
+	assert_eq!(vec.items(), &[0, 1, 2, 7]);
+}
+
+#[test]
+fn append_to_last_row() {
+	let mut vec = JaggedVector::new();
+	vec.push_row(0..2);
+	vec.push_row(None);
+	vec.push_to_last_row(5);
+	vec.extend_last_row(6..8);
+	vec[0][1] = 10;
+	assert_eq!(format!("{:?}", vec), "[[0, 10], [5, 6, 7]]");
+}
+
+#[test]
+#[should_panic]
+fn append_without_rows() {
+	JaggedVector::new().push_to_last_row(0);
+}
+
+#[test]
+fn pop_row() {
+	let mut vec: JaggedVector<Box<i32>> = (0..3).map(|i| (0..i).map(Box::new)).collect();
+	let last = vec.pop_row().unwrap();
+	assert_eq!(last.len(), 2);
+	assert_eq!((*last[0], *last[1]), (0, 1));
+	assert_eq!(vec.len(), 2);
+	assert_eq!(vec.items_len(), 1);
+	vec.pop_row();
+	vec.pop_row();
+	assert!(vec.pop_row().is_none());
+}
+
+#[test]
+fn iterate_and_convert() {
+	let mut rows = Vector::new();
+	for i in 0..4 {
+		rows.push((0..i).collect::<Vector<usize>>());
+	}
+	let vec = JaggedVector::from(rows);
+	assert_eq!(vec.iter().len(), 4);
+	for (i, row) in vec.iter().enumerate() {
+		assert_eq!(row.len(), i);
+	}
+	assert_eq!(vec.iter().next_back(), Some(&[0, 1, 2][..]));
+	let back: Vector<Vector<usize>> = (&vec).into();
+	assert_eq!(back[3].as_slice(), &[0, 1, 2]);
+}
+
+#[test]
+fn push_row_panic() {
+	let mut vec = JaggedVector::new();
+	vec.push_row(0..2);
+	let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+		vec.push_row((2..5).inspect(|&i| assert!(i != 4, "Row panicked")))
+	}));
+	assert!(result.is_err());
+	//The partial row is gone and the last row didn't take its items.
+	assert_eq!(vec.len(), 1);
+	assert_eq!(vec.items(), &[0, 1]);
+	assert_eq!(vec.push_row(7..8), 1);
+	assert_eq!(vec.row(1), &[7]);
+}
@@ -0,0 +1,118 @@
+use crate::*;
+
+#[test]
+fn undo_redo_steps() {
+	let mut vec = JournaledVector::new();
+	vec.push(1);
+	vec.push(2);
+	vec.checkpoint();
+	vec.insert(0, 0);
+	vec.set(2, 20);
+	vec.checkpoint();
+	assert_eq!(vec.remove(1), 1);
+	assert_eq!(vec.pop(), Some(20));
+	assert_eq!(&*vec, &[0]);
+	assert_eq!(vec.undo_len(), 3);
+
+	assert!(vec.undo());
+	assert_eq!(&*vec, &[0, 1, 20]);
+	assert!(vec.undo());
+	assert_eq!(&*vec, &[1, 2]);
+	assert!(vec.undo());
+	assert!(vec.is_empty());
+	assert!(!vec.undo());
+	assert_eq!(vec.redo_len(), 3);
+
+	assert!(vec.redo());
+	assert_eq!(&*vec, &[1, 2]);
+	assert!(vec.redo());
+	assert_eq!(&*vec, &[0, 1, 20]);
+	assert!(vec.redo());
+	assert_eq!(&*vec, &[0]);
+	assert!(!vec.redo());
+}
+
+#[test]
+fn change_clears_redo() {
+	let mut vec = JournaledVector::new();
+	vec.push(1);
+	vec.checkpoint();
+	vec.push(2);
+	assert!(vec.undo());
+	assert_eq!(vec.redo_len(), 1);
+	vec.push(3);
+	assert_eq!(vec.redo_len(), 0);
+	assert!(!vec.redo());
+	assert_eq!(&*vec, &[1, 3]);
+}
+
+#[test]
+fn history_limit() {
+	let mut vec = JournaledVector::with_history_limit(2);
+	for i in 0..5 {
+		vec.push(i);
+		vec.checkpoint();
+	}
+	assert_eq!(vec.undo_len(), 2);
+	assert!(vec.undo());
+	assert!(vec.undo());
+	assert!(!vec.undo());
+	assert_eq!(&*vec, &[0, 1, 2]);
+}
+
+#[test]
+fn history_limit_wraps() {
+	let mut vec = JournaledVector::with_history_limit(3);
+	for i in 0..7 {
+		vec.push(i);
+		vec.checkpoint();
+	}
+	//Undoing and making new checkpoints reuses slots once the ring has wrapped.
+	assert!(vec.undo());
+	vec.push(10);
+	vec.checkpoint();
+	vec.push(11);
+	vec.checkpoint();
+	assert_eq!(vec.undo_len(), 3);
+	assert!(vec.undo());
+	assert!(vec.undo());
+	assert!(vec.redo());
+	assert!(vec.undo());
+	assert!(vec.undo());
+	assert!(!vec.undo());
+	assert_eq!(&*vec, &[0, 1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic(expected = "History limit must be at least 1")]
+fn zero_history_limit() {
+	JournaledVector::<i32>::with_history_limit(0);
+}
+
+#[test]
+fn repeated_undo_redo() {
+	let mut vec: JournaledVector<Box<i32>> = (0..10).map(Box::new).collect::<Vector<_>>().into();
+	for i in 0..10 {
+		vec.set(i, Box::new(-(i as i32)));
+		vec.remove(0);
+		vec.insert(vec.len(), Box::new(i as i32));
+		vec.checkpoint();
+	}
+	let after: Vector<i32> = vec.iter().map(|x| **x).collect();
+	for _ in 0..3 {
+		while vec.undo() {}
+		assert!(vec.iter().map(|x| **x).eq(0..10));
+		while vec.redo() {}
+		assert!(vec.iter().map(|x| **x).eq(after.iter().copied()));
+	}
+	vec.clear_history();
+	assert!(!vec.undo());
+}
+
+#[test]
+#[should_panic(expected = "Index was out of bounds")]
+fn insert_out_of_bounds() {
+	let mut vec = JournaledVector::new();
+	vec.push(1);
+	vec.insert(7, 42);
+}
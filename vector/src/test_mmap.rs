@@ -0,0 +1,98 @@
+use crate::*;
+use std::{fs, io, path::PathBuf};
+
+///A file in the temp directory that is removed again when the test ends.
+struct TempFile(PathBuf);
+
+impl TempFile {
+	fn new(name: &str) -> Self {
+		let path =
+			std::env::temp_dir().join(format!("vector_test_mmap_{}_{}", std::process::id(), name));
+		TempFile(path)
+	}
+}
+
+impl Drop for TempFile {
+	fn drop(&mut self) {
+		let _ = fs::remove_file(&self.0);
+	}
+}
+
+#[test]
+fn push_get() {
+	let file = TempFile::new("push_get");
+	let mut vec = MmapVector::create(&file.0).unwrap();
+	for i in 0..10_000u64 {
+		vec.push(i * 3).unwrap();
+	}
+	assert_eq!(vec.len(), 10_000);
+	assert_eq!(vec[1234], 3702);
+	assert_eq!(vec.get(10_000), None);
+	assert_eq!(
+		vec.iter()
+			.take(3)
+			.copied()
+			.collect::<Vector<_>>()
+			.as_slice(),
+		&[0, 3, 6]
+	);
+	vec[0] = 7;
+	assert_eq!(vec.as_slice()[0], 7);
+	assert_eq!(vec.pop(), Some(29_997));
+}
+
+#[test]
+fn persists() {
+	let file = TempFile::new("persists");
+	{
+		let mut vec = MmapVector::create(&file.0).unwrap();
+		for i in 0..5000 {
+			vec.push([i as f32, -(i as f32)]).unwrap();
+		}
+		vec.pop();
+		vec.flush().unwrap();
+	}
+	let mut vec = MmapVector::<[f32; 2]>::open(&file.0).unwrap();
+	assert_eq!(vec.len(), 4999);
+	assert_eq!(vec[4998], [4998.0, -4998.0]);
+	vec.push([1.0, 2.0]).unwrap();
+	drop(vec);
+	let vec = MmapVector::<[f32; 2]>::open(&file.0).unwrap();
+	assert_eq!(vec.len(), 5000);
+	assert_eq!(vec[4999], [1.0, 2.0]);
+}
+
+#[test]
+fn wrong_type() {
+	let file = TempFile::new("wrong_type");
+	let mut vec = MmapVector::<u32>::create(&file.0).unwrap();
+	vec.push(1).unwrap();
+	drop(vec);
+	let error = MmapVector::<f32>::open(&file.0).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+	let error = MmapVector::<u64>::open(&file.0).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn not_a_vector() {
+	let file = TempFile::new("not_a_vector");
+	fs::write(&file.0, b"hello").unwrap();
+	let error = MmapVector::<u8>::open(&file.0).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn clear() {
+	let file = TempFile::new("clear");
+	let mut vec = MmapVector::create(&file.0).unwrap();
+	for i in 0..100u8 {
+		vec.push(i).unwrap();
+	}
+	let capacity = vec.capacity();
+	vec.clear();
+	assert!(vec.is_empty());
+	assert_eq!(vec.capacity(), capacity);
+	drop(vec);
+	assert!(MmapVector::<u8>::open(&file.0).unwrap().is_empty());
+}
@@ -0,0 +1,88 @@
+use crate::{observable::Change, *};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn change_log() {
+	let mut vec = ObservableVector::new();
+	vec.record_changes();
+	vec.push(1);
+	vec.push(2);
+	vec.insert(0, 0);
+	vec[1] = 10;
+	assert_eq!(vec.remove(2), 2);
+	assert_eq!(vec.pop(), Some(10));
+	vec.clear();
+	let changes = vec.take_changes();
+	assert_eq!(
+		changes.as_slice(),
+		&[
+			Change::Inserted { index: 0 },
+			Change::Inserted { index: 1 },
+			Change::Inserted { index: 0 },
+			Change::Updated { index: 1 },
+			Change::Removed { index: 2, value: 2 },
+			Change::Removed {
+				index: 1,
+				value: 10
+			},
+			Change::Reset,
+		]
+	);
+	assert!(vec.take_changes().is_empty());
+}
+
+#[test]
+fn retain_indices() {
+	let mut vec: ObservableVector<i32> = (0..6).collect::<Vector<_>>().into();
+	vec.record_changes();
+	vec.retain(|x| x % 2 == 0);
+	assert_eq!(&*vec, &[0, 2, 4]);
+	//Replaying the removals one after another gives the same result.
+	let changes = vec.take_changes();
+	assert_eq!(
+		changes.as_slice(),
+		&[
+			Change::Removed { index: 1, value: 1 },
+			Change::Removed { index: 2, value: 3 },
+			Change::Removed { index: 3, value: 5 },
+		]
+	);
+}
+
+#[test]
+fn listeners() {
+	let seen = Rc::new(RefCell::new(Vector::new()));
+	let mut vec = ObservableVector::new();
+	let id = {
+		let seen = Rc::clone(&seen);
+		vec.subscribe(move |change: &Change<i32>| seen.borrow_mut().push(change.clone()))
+	};
+	vec.push(5);
+	vec.set(0, 6);
+	assert!(vec.get_mut(1).is_none());
+	assert!(vec.unsubscribe(id));
+	assert!(!vec.unsubscribe(id));
+	vec.push(7);
+	assert_eq!(
+		seen.borrow().as_slice(),
+		&[Change::Inserted { index: 0 }, Change::Updated { index: 0 }]
+	);
+	assert_eq!(vec.into_vector().as_slice(), &[6, 7]);
+}
+
+#[test]
+fn no_log_without_recording() {
+	let mut vec = ObservableVector::new();
+	vec.push(1);
+	assert!(vec.take_changes().is_empty());
+	assert_eq!(vec.len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Index was out of bounds")]
+fn insert_out_of_bounds() {
+	let mut vec = ObservableVector::new();
+	vec.record_changes();
+	vec.push(1);
+	vec.insert(7, 42);
+}
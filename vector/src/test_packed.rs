@@ -0,0 +1,65 @@
+use crate::*;
+
+#[test]
+fn push_get_straddling() {
+	//12 bits don't divide 64, so some values span two words.
+	let mut vec = PackedIntVector::with_width(12);
+	for i in 0..1000 {
+		vec.push(i * 4 % 4096);
+	}
+	assert_eq!(vec.width(), 12);
+	assert_eq!(vec.len(), 1000);
+	for i in 0..1000 {
+		assert_eq!(vec.get(i as usize), Some(i * 4 % 4096));
+	}
+	assert_eq!(vec.get(1000), None);
+	assert_eq!(vec.words.len(), (1000 * 12usize).div_ceil(64));
+}
+
+#[test]
+fn set_keeps_neighbours() {
+	let mut vec: PackedIntVector = (0..100).map(|i| i % 8).collect();
+	assert_eq!(vec.width(), 3);
+	for i in (0..100).step_by(3) {
+		assert_eq!(vec.set(i, 7 - (i as u32 % 8)), i as u32 % 8);
+	}
+	for i in 0..100u32 {
+		let expected = if i % 3 == 0 { 7 - i % 8 } else { i % 8 };
+		assert_eq!(vec.get(i as usize), Some(expected));
+	}
+}
+
+#[test]
+fn widens() {
+	let mut vec = PackedIntVector::new();
+	vec.push(1);
+	vec.push(0);
+	assert_eq!(vec.width(), 1);
+	vec.push(300);
+	assert_eq!(vec.width(), 9);
+	vec.set(0, u32::MAX);
+	assert_eq!(vec.width(), 32);
+	assert_eq!(vec.to_vector().as_slice(), &[u32::MAX, 0, 300]);
+	//Narrower widths are ignored.
+	vec.widen(4);
+	assert_eq!(vec.width(), 32);
+}
+
+#[test]
+fn pop_clear() {
+	let mut vec: PackedIntVector = (0..70).collect();
+	assert_eq!(vec.pop(), Some(69));
+	assert_eq!(vec.len(), 69);
+	vec.push(5);
+	assert_eq!(vec.get(69), Some(5));
+	vec.clear();
+	assert!(vec.is_empty());
+	assert_eq!(vec.pop(), None);
+	assert_eq!(format!("{:?}", vec), "[]");
+}
+
+#[test]
+#[should_panic]
+fn zero_width() {
+	PackedIntVector::with_width(0);
+}
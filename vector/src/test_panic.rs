@@ -0,0 +1,185 @@
+use crate::*;
+use std::{
+	cell::Cell,
+	panic::{catch_unwind, AssertUnwindSafe},
+	rc::Rc,
+};
+
+//Counts its drops and clones through shared counters and panics in either when told to.
+struct Tracked {
+	id: usize,
+	panic_on_drop: bool,
+	panic_on_clone: bool,
+	drops: Rc<Cell<usize>>,
+	clones: Rc<Cell<usize>>,
+}
+
+impl Drop for Tracked {
+	fn drop(&mut self) {
+		self.drops.set(self.drops.get() + 1);
+		if self.panic_on_drop {
+			panic!("Drop of {} panicked", self.id);
+		}
+	}
+}
+
+impl Clone for Tracked {
+	fn clone(&self) -> Self {
+		if self.panic_on_clone {
+			panic!("Clone of {} panicked", self.id);
+		}
+		self.clones.set(self.clones.get() + 1);
+		Tracked {
+			id: self.id,
+			panic_on_drop: false,
+			panic_on_clone: false,
+			drops: Rc::clone(&self.drops),
+			clones: Rc::clone(&self.clones),
+		}
+	}
+}
+
+//Builds `len` tracked elements, the one at `panic_at` panicking when dropped.
+fn tracked(len: usize, panic_at: Option<usize>) -> (Vector<Tracked>, Rc<Cell<usize>>) {
+	let drops = Rc::new(Cell::new(0));
+	let clones = Rc::new(Cell::new(0));
+	let vec = (0..len)
+		.map(|id| Tracked {
+			id,
+			panic_on_drop: Some(id) == panic_at,
+			panic_on_clone: false,
+			drops: Rc::clone(&drops),
+			clones: Rc::clone(&clones),
+		})
+		.collect();
+	(vec, drops)
+}
+
+#[test]
+fn drop_panic_drops_rest() {
+	let (vec, drops) = tracked(6, Some(2));
+	assert!(catch_unwind(AssertUnwindSafe(|| drop(vec))).is_err());
+	assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn clear_panic_leaves_empty() {
+	let (mut vec, drops) = tracked(6, Some(4));
+	assert!(catch_unwind(AssertUnwindSafe(|| vec.clear())).is_err());
+	assert_eq!(vec.len(), 0);
+	assert_eq!(drops.get(), 6);
+	let (mut other, _) = tracked(1, None);
+	vec.push(other.pop().unwrap());
+	assert_eq!(vec.len(), 1);
+}
+
+#[test]
+fn clone_panic() {
+	let (mut vec, drops) = tracked(5, None);
+	vec[3].panic_on_clone = true;
+	let clones = Rc::clone(&vec[0].clones);
+	assert!(catch_unwind(AssertUnwindSafe(|| vec.clone())).is_err());
+	//The three clones made before the panic are dropped again.
+	assert_eq!(clones.get(), 3);
+	assert_eq!(drops.get(), 3);
+	assert_eq!(vec.len(), 5);
+	drop(vec);
+	assert_eq!(drops.get(), 8);
+}
+
+#[test]
+fn from_iter_panic() {
+	let (source, drops) = tracked(5, None);
+	let result = catch_unwind(AssertUnwindSafe(|| {
+		source
+			.into_iter()
+			.inspect(|elem| assert!(elem.id != 3, "Iterator panicked"))
+			.collect::<Vector<Tracked>>()
+	}));
+	assert!(result.is_err());
+	assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn into_iter_drop_panic() {
+	let (vec, drops) = tracked(6, Some(3));
+	let mut iter = vec.into_iter();
+	drop(iter.next());
+	drop(iter.next_back());
+	assert_eq!(drops.get(), 2);
+	assert!(catch_unwind(AssertUnwindSafe(|| drop(iter))).is_err());
+	assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn retain_predicate_panic() {
+	let (mut vec, drops) = tracked(10, None);
+	let result = catch_unwind(AssertUnwindSafe(|| {
+		vec.retain(|elem| {
+			assert!(elem.id != 5, "Predicate panicked");
+			elem.id % 2 == 0
+		})
+	}));
+	assert!(result.is_err());
+	//1 and 3 were removed, everything from 5 on is kept.
+	assert_eq!(drops.get(), 2);
+	let ids: Vector<usize> = vec.iter().map(|elem| elem.id).collect();
+	assert_eq!(ids.as_slice(), &[0, 2, 4, 5, 6, 7, 8, 9]);
+	drop(vec);
+	assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn retain_drop_panic() {
+	let (mut vec, drops) = tracked(6, Some(3));
+	let result = catch_unwind(AssertUnwindSafe(|| vec.retain(|elem| elem.id % 2 == 0)));
+	assert!(result.is_err());
+	assert_eq!(drops.get(), 2);
+	let ids: Vector<usize> = vec.iter().map(|elem| elem.id).collect();
+	assert_eq!(ids.as_slice(), &[0, 2, 4, 5]);
+	drop(vec);
+	assert_eq!(drops.get(), 6);
+}
+
+#[test]
+fn retain() {
+	let mut vec: Vector<i32> = (0..10).collect();
+	vec.retain(|x| x % 3 != 0);
+	assert_eq!(vec.as_slice(), &[1, 2, 4, 5, 7, 8]);
+	vec.retain(|_| false);
+	assert!(vec.is_empty());
+
+	let mut zst: Vector<()> = (0..5).map(|_| ()).collect();
+	zst.retain(|_| true);
+	assert_eq!(zst.len(), 5);
+}
+
+#[test]
+#[should_panic(expected = "Index was out of bounds")]
+fn insert_out_of_bounds() {
+	let mut vec = Vector::new();
+	vec.push(1);
+	vec.insert(7, 42);
+}
+
+#[test]
+fn retain_zst() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+	static DROPS: AtomicUsize = AtomicUsize::new(0);
+	static CALLS: AtomicUsize = AtomicUsize::new(0);
+	struct Marker;
+	impl Drop for Marker {
+		fn drop(&mut self) {
+			DROPS.fetch_add(1, Ordering::Relaxed);
+		}
+	}
+
+	//Elements the predicate returns true for are kept, the rest are dropped.
+	let mut vec: Vector<Marker> = (0..10).map(|_| Marker).collect();
+	vec.retain(|_| true);
+	assert_eq!((vec.len(), DROPS.load(Ordering::Relaxed)), (10, 0));
+	vec.retain(|_| CALLS.fetch_add(1, Ordering::Relaxed).is_multiple_of(2));
+	assert_eq!((vec.len(), DROPS.load(Ordering::Relaxed)), (5, 5));
+	vec.retain(|_| false);
+	assert_eq!((vec.len(), DROPS.load(Ordering::Relaxed)), (0, 10));
+}
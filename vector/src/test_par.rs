@@ -0,0 +1,192 @@
+use crate::*;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+//Large enough to be split between threads.
+const LEN: u64 = 100_000;
+//Set explicitly so the work is split even on machines with a single core.
+const THREADS: usize = 4;
+
+#[test]
+fn for_each_mut() {
+	let mut vec: Vector<u64> = (0..LEN).collect();
+	vec.par_for_each_mut_on(THREADS, |x| *x *= 2);
+	vec.par_for_each_mut_on(1, |x| *x += 1);
+	assert!(vec.iter().copied().eq((0..LEN).map(|x| x * 2 + 1)));
+	vec.par_for_each_mut(|x| *x -= 1);
+	assert!(vec.iter().copied().eq((0..LEN).map(|x| x * 2)));
+
+	let mut small: Vector<u64> = (0..10).collect();
+	small.par_for_each_mut(|x| *x += 1);
+	assert!(small.iter().copied().eq(1..11));
+
+	let mut empty: Vector<u64> = Vector::new();
+	empty.par_for_each_mut(|_| unreachable!());
+}
+
+#[test]
+fn map() {
+	let vec: Vector<u64> = (0..LEN).collect();
+	let mapped = vec.par_map_on(THREADS, |x| Box::new(x.to_string()));
+	assert_eq!(mapped.len(), LEN as usize);
+	assert!(mapped.iter().map(|s| s.parse::<u64>().unwrap()).eq(0..LEN));
+	assert!(vec.par_map(|x| x + 1).iter().copied().eq(1..=LEN));
+	assert!(Vector::<u8>::new().par_map(|x| *x).is_empty());
+}
+
+#[test]
+fn sort() {
+	//A simple linear congruential generator keeps the test deterministic.
+	let mut state = 12345u64;
+	let mut vec: Vector<(u16, u64)> = (0..LEN)
+		.map(|i| {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+			((state >> 48) as u16, i)
+		})
+		.collect();
+	let mut expected = vec.clone();
+	expected.as_slice_mut().sort();
+	let mut sequential = vec.clone();
+	vec.par_sort_on(THREADS);
+	assert_eq!(vec.as_slice(), expected.as_slice());
+	sequential.par_sort();
+	assert_eq!(sequential.as_slice(), expected.as_slice());
+
+	//Stable, so equal keys keep their order.
+	let mut pairs: Vector<Pair> = (0..LEN).map(|i| Pair(i % 7, i)).collect();
+	pairs.par_sort_on(THREADS);
+	assert!(pairs
+		.as_slice()
+		.windows(2)
+		.all(|w| w[0].0 < w[1].0 || w[0].1 < w[1].1));
+}
+
+//Ordered by the first field only.
+#[derive(Debug)]
+struct Pair(u64, u64);
+
+impl PartialEq for Pair {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl Eq for Pair {}
+
+impl PartialOrd for Pair {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Pair {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.0.cmp(&other.0)
+	}
+}
+
+#[test]
+fn reduce() {
+	let vec: Vector<u64> = (0..LEN).collect();
+	assert_eq!(
+		vec.par_reduce_on(THREADS, || 0, |a, b| a + b),
+		LEN * (LEN - 1) / 2
+	);
+	assert_eq!(vec.par_reduce(|| 0, |a, b| a + b), LEN * (LEN - 1) / 2);
+	assert_eq!(vec.par_reduce_on(THREADS, || 0, u64::max), LEN - 1);
+	assert_eq!(Vector::<u64>::new().par_reduce(|| 7, |a, b| a + b), 7);
+
+	//Non commutative, so chunks must be combined in order.
+	let words: Vector<String> = (0..LEN).map(|i| (i % 10).to_string()).collect();
+	let joined = words.par_reduce_on(THREADS, String::new, |a, b| a + &b);
+	assert_eq!(joined, words.iter().map(String::as_str).collect::<String>());
+}
+
+#[test]
+fn worker_panic() {
+	let mut vec: Vector<u64> = (0..LEN).collect();
+	let result = catch_unwind(AssertUnwindSafe(|| {
+		vec.par_for_each_mut_on(THREADS, |x| assert!(*x != LEN / 2, "Worker panicked"))
+	}));
+	assert!(result.is_err());
+	assert_eq!(vec.len(), LEN as usize);
+	let result = catch_unwind(AssertUnwindSafe(|| {
+		vec.par_map_on(THREADS, |x| {
+			assert!(*x != LEN - 1, "Worker panicked");
+			*x
+		})
+	}));
+	assert!(result.is_err());
+}
+
+#[test]
+fn sort_uneven_runs() {
+	//Run counts that aren't powers of two leave a run without a partner in some rounds.
+	for threads in [3, 5, 7] {
+		let mut vec: Vector<u64> = (0..LEN).map(|i| (i * 7919) % 10_007).collect();
+		let mut expected = vec.clone();
+		expected.as_slice_mut().sort();
+		vec.par_sort_on(threads);
+		assert_eq!(vec.as_slice(), expected.as_slice());
+	}
+}
+
+#[test]
+fn sort_zst() {
+	let len = par::SEQUENTIAL_THRESHOLD * 2 + 1;
+	let mut vec: Vector<()> = (0..len).map(|_| ()).collect();
+	vec.par_sort_on(THREADS);
+	vec.par_sort();
+	assert_eq!(vec.len(), len);
+}
+
+//Counts drops and panics when 0 is compared to `LEN / 2`, which only happens while merging.
+struct Fragile(u64);
+
+static FRAGILE_DROPS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+impl Drop for Fragile {
+	fn drop(&mut self) {
+		FRAGILE_DROPS.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+	}
+}
+
+impl PartialEq for Fragile {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+
+impl Eq for Fragile {}
+
+impl PartialOrd for Fragile {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Fragile {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		let pair = (self.0.min(other.0), self.0.max(other.0));
+		assert!(pair != (0, LEN / 2), "Comparison panicked");
+		self.0.cmp(&other.0)
+	}
+}
+
+#[test]
+fn sort_comparison_panic() {
+	//Reversed, so the four sorted chunks hold 0 and `LEN / 2` in different chunks and the last
+	// merge compares them first.
+	let mut vec: Vector<Fragile> = (0..LEN).rev().map(Fragile).collect();
+	let result = catch_unwind(AssertUnwindSafe(|| vec.par_sort_on(THREADS)));
+	assert!(result.is_err());
+	//Every element is still there exactly once.
+	let mut values: Vector<u64> = vec.iter().map(|elem| elem.0).collect();
+	values.as_slice_mut().sort();
+	assert!(values.iter().copied().eq(0..LEN));
+	assert_eq!(FRAGILE_DROPS.load(std::sync::atomic::Ordering::Relaxed), 0);
+	drop(vec);
+	assert_eq!(
+		FRAGILE_DROPS.load(std::sync::atomic::Ordering::Relaxed),
+		LEN as usize
+	);
+}
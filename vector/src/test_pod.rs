@@ -0,0 +1,132 @@
+use crate::*;
+use std::io;
+
+fn pod_error(error: io::Error) -> PodError {
+	assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+	*error
+		.into_inner()
+		.expect("No inner error")
+		.downcast::<PodError>()
+		.expect("Not a PodError")
+}
+
+#[test]
+fn round_trip() {
+	let vec: Vector<f64> = (0..1000).map(|i| i as f64 * 0.5).collect();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	assert_eq!(file.len(), 32 + 8000);
+	let read = Vector::<f64>::read_from(&mut file.as_slice()).unwrap();
+	assert_eq!(read.as_slice(), vec.as_slice());
+}
+
+#[test]
+fn round_trip_arrays() {
+	let vec: Vector<[u16; 3]> = (0..10).map(|i| [i, i + 1, i + 2]).collect();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	let read = Vector::<[u16; 3]>::read_from(&mut file.as_slice()).unwrap();
+	assert_eq!(read.as_slice(), vec.as_slice());
+}
+
+#[test]
+fn empty() {
+	let vec = Vector::<u32>::new();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	let read = Vector::<u32>::read_from(&mut file.as_slice()).unwrap();
+	assert!(read.is_empty());
+}
+
+#[test]
+fn wrong_type() {
+	let vec: Vector<u32> = (0..10).collect();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	let error = Vector::<f32>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(
+		pod_error(error),
+		PodError::Kind {
+			expected: 3,
+			found: 1
+		}
+	);
+	let error = Vector::<u64>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(
+		pod_error(error),
+		PodError::ElementSize {
+			expected: 8,
+			found: 4
+		}
+	);
+	let error = Vector::<[u8; 4]>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(
+		pod_error(error),
+		PodError::Alignment {
+			expected: 1,
+			found: 4
+		}
+	);
+}
+
+#[test]
+fn wrong_endianness() {
+	let vec: Vector<u32> = (0..10).collect();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	file[17] ^= 1;
+	let error = Vector::<u32>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(pod_error(error), PodError::Endianness);
+}
+
+#[test]
+fn bad_magic() {
+	let mut file = Vector::new();
+	file.extend_from_slice(&[0; 32]);
+	let error = Vector::<u8>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(pod_error(error), PodError::Magic);
+}
+
+#[test]
+fn truncated() {
+	let vec: Vector<u32> = (0..10).collect();
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	let error = Vector::<u32>::read_from(&mut &file.as_slice()[..40]).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+//Writes a dump of `vec` whose header claims `len` elements.
+fn with_claimed_len(vec: &Vector<u32>, len: u64) -> Vector<u8> {
+	let mut file = Vector::new();
+	vec.write_to(&mut file).unwrap();
+	file.as_slice_mut()[24..32].copy_from_slice(&len.to_le_bytes());
+	file
+}
+
+#[test]
+fn overflowing_length() {
+	let vec: Vector<u32> = (0..10).collect();
+	let claim = (usize::MAX / mem::size_of::<u32>()) as u64;
+	let file = with_claimed_len(&vec, claim);
+	let error = Vector::<u32>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(pod_error(error), PodError::Length);
+}
+
+#[test]
+fn length_beyond_data() {
+	//Far more than could be allocated, but only ten elements follow.
+	let vec: Vector<u32> = (0..10).collect();
+	let file = with_claimed_len(&vec, 1 << 40);
+	let error = Vector::<u32>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+	//Spans several read chunks.
+	let vec: Vector<u32> = (0..100_000).collect();
+	let file = with_claimed_len(&vec, 100_001);
+	let error = Vector::<u32>::read_from(&mut file.as_slice()).unwrap_err();
+	assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+	let file = with_claimed_len(&vec, 100_000);
+	let read = Vector::<u32>::read_from(&mut file.as_slice()).unwrap();
+	assert_eq!(read.as_slice(), vec.as_slice());
+}
@@ -0,0 +1,68 @@
+use crate::*;
+
+#[test]
+fn reuses_allocation() {
+	let pool = VectorPool::new(1 << 16);
+	let ptr = {
+		let mut buf = pool.get(100);
+		assert_eq!(buf.capacity(), 128);
+		buf.extend_from_slice(&[1u8, 2, 3]);
+		buf.as_ptr()
+	};
+	assert_eq!(pool.len(), 1);
+	assert_eq!(pool.retained_bytes(), 128);
+	let buf = pool.get(128);
+	assert!(buf.is_empty());
+	assert_eq!(buf.as_ptr(), ptr);
+	assert!(pool.is_empty());
+	assert_eq!(pool.retained_bytes(), 0);
+}
+
+#[test]
+fn buckets_by_capacity() {
+	let pool = VectorPool::<u32>::new(1 << 16);
+	drop(pool.get(10));
+	//A 16 element vector can't serve a request for 20.
+	let big = pool.get(20);
+	assert_eq!(big.capacity(), 32);
+	assert_eq!(pool.len(), 1);
+	drop(big);
+	//Smaller requests take from larger buckets once their own is empty.
+	assert_eq!(pool.get(2).capacity(), 16);
+	assert_eq!(pool.get(2).capacity(), 16);
+	let (a, b, c) = (pool.get(2), pool.get(2), pool.get(2));
+	assert_eq!((a.capacity(), b.capacity(), c.capacity()), (16, 32, 2));
+}
+
+#[test]
+fn retained_limit() {
+	let pool = VectorPool::<u64>::new(1024);
+	let bufs: Vector<_> = (0..4).map(|_| pool.get(64)).collect();
+	drop(bufs);
+	//Each buffer is 512 bytes, so only two fit.
+	assert_eq!(pool.len(), 2);
+	assert_eq!(pool.retained_bytes(), 1024);
+	pool.clear();
+	assert!(pool.is_empty());
+	assert_eq!(pool.retained_bytes(), 0);
+}
+
+#[test]
+fn detach_and_put() {
+	let pool = VectorPool::new(1 << 16);
+	let mut buf = pool.get(4);
+	buf.push(Box::new(1));
+	let vec = buf.detach();
+	assert_eq!(pool.len(), 0);
+	assert_eq!(*vec[0], 1);
+	pool.put(vec);
+	assert_eq!(pool.len(), 1);
+	assert!(pool.get(4).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Overflow")]
+fn get_overflow() {
+	let pool: VectorPool<u8> = VectorPool::new(1 << 16);
+	pool.get(usize::MAX / 2 + 2);
+}
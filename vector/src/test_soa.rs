@@ -0,0 +1,77 @@
+use crate::*;
+
+crate::soa_vector! {
+	#[derive(Debug, Clone, PartialEq)]
+	pub struct Record {
+		pub id: u32,
+		pub name: Box<str>,
+		pub score: f64,
+	}
+	pub struct RecordVector, RecordRef, RecordMut, RecordSlicesMut;
+}
+
+fn record(id: u32) -> Record {
+	Record {
+		id,
+		name: format!("record {}", id).into_boxed_str(),
+		score: id as f64 / 2.0,
+	}
+}
+
+#[test]
+fn push_get() {
+	let mut vec = RecordVector::new();
+	vec.push(record(1));
+	vec.push(record(2));
+	assert_eq!(vec.len(), 2);
+	let second = vec.get(1).unwrap();
+	assert_eq!(*second.id, 2);
+	assert_eq!(&**second.name, "record 2");
+	assert_eq!(*second.score, 1.0);
+	assert!(vec.get(2).is_none());
+}
+
+#[test]
+fn columns() {
+	let vec: RecordVector = (0..5).map(record).collect();
+	assert_eq!(vec.id(), &[0, 1, 2, 3, 4]);
+	assert_eq!(vec.score(), &[0.0, 0.5, 1.0, 1.5, 2.0]);
+	assert_eq!(vec.score().iter().sum::<f64>(), 5.0);
+}
+
+#[test]
+fn mutate() {
+	let mut vec: RecordVector = (0..3).map(record).collect();
+	*vec.get_mut(1).unwrap().score = 10.0;
+	let slices = vec.as_slices_mut();
+	for (id, score) in slices.id.iter_mut().zip(slices.score.iter()) {
+		*id += *score as u32;
+	}
+	assert_eq!(vec.id(), &[0, 11, 3]);
+}
+
+#[test]
+fn pop_remove() {
+	let mut vec: RecordVector = (0..4).map(record).collect();
+	assert_eq!(vec.pop(), Some(record(3)));
+	assert_eq!(vec.remove(0), record(0));
+	assert_eq!(vec.id(), &[1, 2]);
+	assert_eq!(&*vec.name()[0], "record 1");
+	vec.clear();
+	assert!(vec.is_empty());
+	assert_eq!(vec.pop(), None);
+}
+
+#[test]
+#[should_panic]
+fn remove_out_of_bounds() {
+	let mut vec: RecordVector = (0..2).map(record).collect();
+	vec.remove(2);
+}
+
+#[test]
+fn iter() {
+	let vec: RecordVector = (0..3).map(record).collect();
+	let ids: Vector<u32> = vec.iter().rev().map(|r| *r.id).collect();
+	assert_eq!(ids.as_slice(), &[2, 1, 0]);
+}
@@ -0,0 +1,73 @@
+use crate::*;
+
+#[test]
+fn get_set() {
+	let mut vec = SparseVector::new(1000);
+	assert_eq!(vec.len(), 1000);
+	assert_eq!(vec.get(999), Some(&0));
+	assert_eq!(vec.get(1000), None);
+	assert_eq!(vec.set(500, 3), 0);
+	assert_eq!(vec.set(10, 1), 0);
+	assert_eq!(vec.set(500, 4), 3);
+	assert_eq!(vec[500], 4);
+	assert_eq!(vec.stored_len(), 2);
+	//Setting back to the default removes the entry.
+	assert_eq!(vec.set(10, 0), 1);
+	assert_eq!(vec.set(20, 0), 0);
+	assert_eq!(vec.stored_len(), 1);
+	let entries: Vector<(usize, i32)> = vec.iter().map(|(i, &x)| (i, x)).collect();
+	assert_eq!(entries.as_slice(), &[(500, 4)]);
+}
+
+#[test]
+#[should_panic]
+fn set_out_of_bounds() {
+	SparseVector::new(3).set(3, 1);
+}
+
+#[test]
+fn dense_round_trip() {
+	let dense: Vector<i32> = (0..10).map(|i| if i % 4 == 1 { i } else { 0 }).collect();
+	let mut sparse = SparseVector::from(dense);
+	assert_eq!(sparse.stored_len(), 3);
+	sparse.push(0);
+	sparse.push(7);
+	assert_eq!(sparse.len(), 12);
+	assert_eq!(
+		sparse.to_dense().as_slice(),
+		&[0, 1, 0, 0, 0, 5, 0, 0, 0, 9, 0, 7]
+	);
+	assert_eq!(
+		format!("{:?}", sparse),
+		"SparseVector(12) {1: 1, 5: 5, 9: 9, 11: 7}"
+	);
+}
+
+#[test]
+fn dot_and_add() {
+	let mut a = SparseVector::new(100);
+	let mut b = SparseVector::new(100);
+	a.set(1, 2.0);
+	a.set(50, 3.0);
+	a.set(99, 1.0);
+	b.set(50, 4.0);
+	b.set(60, 5.0);
+	b.set(99, -1.0);
+	assert_eq!(a.dot(&b), 11.0);
+
+	let sum = a.add(&b);
+	//99 cancels out and isn't stored.
+	let entries: Vector<(usize, f64)> = sum.iter().map(|(i, &x)| (i, x)).collect();
+	assert_eq!(entries.as_slice(), &[(1, 2.0), (50, 7.0), (60, 5.0)]);
+	assert_eq!(sum, b.add(&a));
+}
+
+#[test]
+fn clear_keeps_len() {
+	let mut vec = SparseVector::new(100);
+	vec.set(3, 1.5);
+	vec.clear();
+	assert_eq!(vec.stored_len(), 0);
+	assert_eq!(vec.len(), 100);
+	assert!(!vec.is_empty());
+}
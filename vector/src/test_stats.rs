@@ -0,0 +1,62 @@
+use crate::*;
+
+#[test]
+fn allocated_bytes() {
+	let mut vec: Vector<u32> = Vector::new();
+	assert_eq!(vec.allocated_bytes(), 0);
+	vec.push(1);
+	assert_eq!(vec.allocated_bytes(), vec.capacity() * 4);
+	let big: Vector<u64> = Vector::with_capacity(100);
+	assert_eq!(big.allocated_bytes(), 800);
+
+	let aligned: Vector<u8, 64> = Vector::with_capacity_aligned(3);
+	assert_eq!(aligned.allocated_bytes(), 3);
+
+	let mut zst: Vector<()> = Vector::new();
+	zst.push(());
+	assert_eq!(zst.allocated_bytes(), 0);
+}
+
+//The counters are shared with every other test running at the same time, so only lower bounds
+// can be checked. Tests reading them hold the lock so `reset` can't lower them in between.
+#[cfg(feature = "alloc-stats")]
+static STATS_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+#[cfg(feature = "alloc-stats")]
+#[test]
+fn counts_allocations() {
+	let _lock = STATS_LOCK.lock().unwrap();
+	let before = stats::snapshot();
+	let mut vec: Vector<u64> = Vector::with_capacity(1000);
+	let during = stats::snapshot();
+	assert!(during.allocations > before.allocations);
+	assert!(during.peak_bytes >= 8000);
+	vec.reserve_additional(1000);
+	assert!(stats::snapshot().reallocations > before.reallocations);
+	drop(vec);
+	let after = stats::snapshot();
+	assert!(after.deallocations >= before.deallocations + 2);
+}
+
+#[cfg(feature = "alloc-stats")]
+#[test]
+fn counts_iterator_drop() {
+	let _lock = STATS_LOCK.lock().unwrap();
+	let vec: Vector<u8> = (0..100).collect();
+	let mut iter = vec.into_iter();
+	iter.next();
+	let before = stats::snapshot().deallocations;
+	drop(iter);
+	assert!(stats::snapshot().deallocations > before);
+}
+
+#[cfg(feature = "alloc-stats")]
+#[test]
+fn reset_keeps_live_bytes() {
+	let _lock = STATS_LOCK.lock().unwrap();
+	let _held: Vector<u8> = Vector::with_capacity(1 << 20);
+	stats::reset();
+	let stats = stats::snapshot();
+	assert!(stats.live_bytes >= 1 << 20);
+	assert!(stats.peak_bytes >= 1 << 20);
+}
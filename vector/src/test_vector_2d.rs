@@ -0,0 +1,127 @@
+use crate::*;
+
+fn grid() -> Vector2D<i32> {
+	//[[0, 1, 2], [10, 11, 12]]
+	Vector2D::from_fn(2, 3, |r, c| (r * 10 + c) as i32)
+}
+
+#[test]
+fn index() {
+	let mut grid = grid();
+	assert_eq!(grid[(1, 2)], 12);
+	grid[(0, 1)] = 5;
+	assert_eq!(grid.as_slice(), &[0, 5, 2, 10, 11, 12]);
+	assert_eq!(grid.get(2, 0), None);
+	assert_eq!(grid.get(0, 3), None);
+}
+
+#[test]
+fn rows() {
+	let mut grid = grid();
+	assert_eq!(grid.row(1), &[10, 11, 12]);
+	grid.row_mut(0)[2] = 7;
+	assert_eq!(grid.row(0), &[0, 1, 7]);
+}
+
+#[test]
+fn column() {
+	let mut grid = grid();
+	let col: Vector<_> = grid.column(1).copied().collect();
+	assert_eq!(col.as_slice(), &[1, 11]);
+	let col: Vector<_> = grid.column(2).rev().copied().collect();
+	assert_eq!(col.as_slice(), &[12, 2]);
+	for elem in grid.column_mut(0) {
+		*elem += 100;
+	}
+	assert_eq!(grid.as_slice(), &[100, 1, 2, 110, 11, 12]);
+}
+
+#[test]
+fn transpose() {
+	let mut grid = grid();
+	grid.transpose();
+	assert_eq!(grid.rows(), 3);
+	assert_eq!(grid.cols(), 2);
+	assert_eq!(grid.as_slice(), &[0, 10, 1, 11, 2, 12]);
+	grid.transpose();
+	assert_eq!(grid.as_slice(), &[0, 1, 2, 10, 11, 12]);
+}
+
+#[test]
+fn insert_remove_row() {
+	let mut grid = grid();
+	grid.insert_row(1, vec![5, 6, 7]);
+	assert_eq!(grid.as_slice(), &[0, 1, 2, 5, 6, 7, 10, 11, 12]);
+	grid.insert_row(3, vec![20, 21, 22]);
+	assert_eq!(grid.row(3), &[20, 21, 22]);
+	let removed = grid.remove_row(0);
+	assert_eq!(removed.as_slice(), &[0, 1, 2]);
+	assert_eq!(grid.rows(), 3);
+	assert_eq!(grid.as_slice(), &[5, 6, 7, 10, 11, 12, 20, 21, 22]);
+}
+
+#[test]
+fn insert_remove_col() {
+	let mut grid = grid();
+	grid.insert_col(0, vec![-1, -2]);
+	assert_eq!(grid.as_slice(), &[-1, 0, 1, 2, -2, 10, 11, 12]);
+	grid.insert_col(4, vec![3, 13]);
+	assert_eq!(grid.row(1), &[-2, 10, 11, 12, 13]);
+	let removed = grid.remove_col(2);
+	assert_eq!(removed.as_slice(), &[1, 11]);
+	assert_eq!(grid.as_slice(), &[-1, 0, 2, 3, -2, 10, 12, 13]);
+}
+
+#[test]
+fn build_from_empty() {
+	let mut grid = Vector2D::new();
+	grid.insert_row(0, vec![1, 2]);
+	grid.insert_row(1, vec![3, 4]);
+	assert_eq!(grid.cols(), 2);
+	assert_eq!(grid.as_slice(), &[1, 2, 3, 4]);
+	let mut grid = Vector2D::new();
+	grid.insert_col(0, vec![1, 3]);
+	grid.insert_col(1, vec![2, 4]);
+	assert_eq!(grid.rows(), 2);
+	assert_eq!(grid.as_slice(), &[1, 2, 3, 4]);
+}
+
+#[test]
+#[should_panic]
+fn insert_row_wrong_length() {
+	let mut grid = grid();
+	grid.insert_row(0, vec![1, 2]);
+}
+
+#[test]
+fn resize() {
+	let mut grid = grid();
+	grid.resize(3, 2, -1);
+	assert_eq!(grid.as_slice(), &[0, 1, 10, 11, -1, -1]);
+	grid.resize(1, 4, 9);
+	assert_eq!(grid.as_slice(), &[0, 1, 9, 9]);
+	grid.resize(0, 0, 0);
+	assert!(grid.is_empty());
+}
+
+#[test]
+fn drop() {
+	static mut SUM: i32 = 0;
+	#[derive(Clone, Debug)]
+	struct ToDrop;
+	impl Drop for ToDrop {
+		fn drop(&mut self) {
+			unsafe {
+				SUM += 1;
+			}
+		}
+	}
+	{
+		let mut grid = Vector2D::from_fn(3, 3, |_, _| ToDrop);
+		grid.transpose();
+		let _ = grid.remove_row(0);
+		let _ = grid.remove_col(0);
+		assert_eq!(unsafe { SUM }, 5);
+	}
+	assert_eq!(unsafe { SUM }, 9);
+}
@@ -1,5 +1,6 @@
 use crate::*;
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Copy, Clone, Debug, PartialEq)]
 struct ZST;
 
@@ -106,7 +107,7 @@ fn borrowed_iterator() {
 	vec.push(ZST);
 	vec.push(ZST);
 	vec.push(ZST);
-	let std_vec = vec![ZST, ZST, ZST, ZST, ZST];
+	let std_vec = [ZST, ZST, ZST, ZST, ZST];
 	let mut std_iter = std_vec.iter();
 	let mut iter = vec.iter();
 	for _ in 0..6 {
@@ -122,7 +123,7 @@ fn borrowed_back_iterator() {
 	vec.push(ZST);
 	vec.push(ZST);
 	vec.push(ZST);
-	let std_vec = vec![ZST, ZST, ZST, ZST, ZST];
+	let std_vec = [ZST, ZST, ZST, ZST, ZST];
 	let mut std_iter = std_vec.iter().rev();
 	let mut iter = vec.iter().rev();
 	for _ in 0..6 {
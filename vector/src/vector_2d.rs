@@ -0,0 +1,409 @@
+use crate::*;
+use std::marker::PhantomData;
+
+///A dense row-major grid of `T` stored in a single `Vector<T>`. Element `(row, col)` lives at
+/// `row * cols + col`.
+pub struct Vector2D<T> {
+	pub(crate) data: Vector<T>,
+	pub(crate) rows: usize,
+	pub(crate) cols: usize,
+}
+
+impl<T> Default for Vector2D<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Debug> Debug for Vector2D<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_list()
+			.entries((0..self.rows).map(|r| self.row(r)))
+			.finish()
+	}
+}
+
+impl<T> Index<(usize, usize)> for Vector2D<T> {
+	type Output = T;
+	fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+		self.get(row, col).expect("Index was out of bounds")
+	}
+}
+
+impl<T> IndexMut<(usize, usize)> for Vector2D<T> {
+	fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+		self.get_mut(row, col).expect("Index was out of bounds")
+	}
+}
+
+impl<T> Vector2D<T> {
+	///Creates a new grid with no rows or columns. Does not allocate till it's needed.
+	pub fn new() -> Self {
+		Vector2D {
+			data: Vector::new(),
+			rows: 0,
+			cols: 0,
+		}
+	}
+
+	///Creates a new grid of `rows` by `cols` copies of `value`.
+	///
+	/// Panics if `rows * cols` overflows a `usize`. Has O(n) complexity.
+	pub fn from_elem(rows: usize, cols: usize, value: T) -> Self
+	where
+		T: Clone,
+	{
+		Vector2D::from_fn(rows, cols, |_, _| value.clone())
+	}
+
+	///Creates a new grid of `rows` by `cols` where each element is `f(row, col)`.
+	///
+	/// Panics if `rows * cols` overflows a `usize`. Has O(n) complexity.
+	pub fn from_fn<F: FnMut(usize, usize) -> T>(rows: usize, cols: usize, mut f: F) -> Self {
+		let len = rows.checked_mul(cols).expect("Overflow");
+		let mut data = Vector::with_capacity(len);
+		for r in 0..rows {
+			for c in 0..cols {
+				data.push(f(r, c));
+			}
+		}
+		Vector2D { data, rows, cols }
+	}
+
+	///Wraps a row-major `Vector` as a grid of `rows` by `cols`.
+	///
+	/// Panics if the length of `data` isn't `rows * cols`. Has O(1) complexity.
+	pub fn from_vector(rows: usize, cols: usize, data: Vector<T>) -> Self {
+		assert_eq!(
+			rows.checked_mul(cols),
+			Some(data.len()),
+			"Vector length doesn't match the dimensions"
+		);
+		Vector2D { data, rows, cols }
+	}
+
+	///Returns the underlying row-major `Vector`.
+	///
+	/// Has O(1) complexity.
+	pub fn into_vector(self) -> Vector<T> {
+		self.data
+	}
+
+	///Returns the amount of rows.
+	pub fn rows(&self) -> usize {
+		self.rows
+	}
+
+	///Returns the amount of columns.
+	pub fn cols(&self) -> usize {
+		self.cols
+	}
+
+	///Returns the total amount of elements stored in the grid.
+	pub fn len(&self) -> usize {
+		self.data.len()
+	}
+
+	///Checks if the grid has no elements in it.
+	pub fn is_empty(&self) -> bool {
+		self.data.is_empty()
+	}
+
+	///Gets a reference to the element at `(row, col)`.
+	///
+	/// Returns `None` if either index is out of bounds. Has complexity O(1).
+	pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+		if row >= self.rows || col >= self.cols {
+			return None;
+		}
+		self.data.get(row * self.cols + col)
+	}
+
+	///Gets a mutable reference to the element at `(row, col)`.
+	///
+	/// Returns `None` if either index is out of bounds. Has complexity O(1).
+	pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+		if row >= self.rows || col >= self.cols {
+			return None;
+		}
+		self.data.get_mut(row * self.cols + col)
+	}
+
+	///Borrows the whole grid as a row-major slice.
+	///
+	/// Has complexity O(1).
+	pub fn as_slice(&self) -> &[T] {
+		self.data.as_slice()
+	}
+
+	///Borrows the whole grid as a mutable row-major slice.
+	///
+	/// Has complexity O(1).
+	pub fn as_slice_mut(&mut self) -> &mut [T] {
+		self.data.as_slice_mut()
+	}
+
+	///Borrows a row as a slice.
+	///
+	/// Panics if `row` is out of bounds. Has complexity O(1).
+	pub fn row(&self, row: usize) -> &[T] {
+		assert!(row < self.rows, "Row was out of bounds");
+		let start = row * self.cols;
+		&self.data.as_slice()[start..start + self.cols]
+	}
+
+	///Borrows a row as a mutable slice.
+	///
+	/// Panics if `row` is out of bounds. Has complexity O(1).
+	pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+		assert!(row < self.rows, "Row was out of bounds");
+		let start = row * self.cols;
+		let cols = self.cols;
+		&mut self.data.as_slice_mut()[start..start + cols]
+	}
+
+	///Returns an iterator over a column, top to bottom.
+	///
+	/// Panics if `col` is out of bounds. Has complexity O(1).
+	pub fn column(&self, col: usize) -> Column<'_, T> {
+		assert!(col < self.cols, "Column was out of bounds");
+		Column {
+			data: self.data.as_slice().get(col..).unwrap_or(&[]),
+			stride: self.cols,
+			front: 0,
+			back: self.rows,
+		}
+	}
+
+	///Returns an iterator over mutable references to a column, top to bottom.
+	///
+	/// Panics if `col` is out of bounds. Has complexity O(1).
+	pub fn column_mut(&mut self, col: usize) -> ColumnMut<'_, T> {
+		assert!(col < self.cols, "Column was out of bounds");
+		let stride = self.cols;
+		let back = self.rows;
+		ColumnMut {
+			//Never dereferenced if there are no rows, so wrapping past the end is fine.
+			ptr: self.data.as_slice_mut().as_mut_ptr().wrapping_add(col),
+			stride,
+			front: 0,
+			back,
+			_marker: PhantomData,
+		}
+	}
+
+	///Swaps rows and columns, moving every element once.
+	///
+	/// Has O(n) complexity.
+	pub fn transpose(&mut self) {
+		let (rows, cols) = (self.rows, self.cols);
+		let mut old = mem::take(&mut self.data);
+		let mut new = Vector::with_capacity(old.len());
+		if !old.is_empty() {
+			let ptr = old
+				.as_ptr_mut()
+				.expect("Non-empty vector without a buffer?");
+			//Safety: Every element is read exactly once below, so the old vector must
+			// not drop them again. Its allocation is still freed when it goes out of scope.
+			unsafe { old.set_len(0) };
+			for c in 0..cols {
+				for r in 0..rows {
+					//Safety: `r * cols + c` is within the old length.
+					new.push(unsafe { ptr.add(r * cols + c).read() });
+				}
+			}
+		}
+		self.data = new;
+		self.rows = cols;
+		self.cols = rows;
+	}
+
+	///Inserts a row at index `row`, moving every row after it down.
+	/// If the grid has no rows, the new row decides the amount of columns.
+	///
+	/// Panics if `row` is greater than the amount of rows or the row has the wrong length. Has O(n) complexity.
+	pub fn insert_row<I: IntoIterator<Item = T>>(&mut self, row: usize, elems: I) {
+		assert!(row <= self.rows, "Row was out of bounds");
+		let elems: Vector<T> = elems.into_iter().collect();
+		if self.rows == 0 {
+			self.cols = elems.len();
+		}
+		assert_eq!(elems.len(), self.cols, "Row has the wrong length");
+		let len = self.data.len();
+		let mut old = mem::take(&mut self.data).into_iter();
+		let mut new = Vector::with_capacity(len + self.cols);
+		for _ in 0..row * self.cols {
+			new.push(old.next().expect("Grid was shorter than its dimensions?"));
+		}
+		for elem in elems {
+			new.push(elem);
+		}
+		for elem in old {
+			new.push(elem);
+		}
+		self.data = new;
+		self.rows += 1;
+	}
+
+	///Removes the row at index `row`, moving every row after it up.
+	///
+	/// Panics if `row` is out of bounds. Has O(n) complexity.
+	pub fn remove_row(&mut self, row: usize) -> Vector<T> {
+		assert!(row < self.rows, "Row was out of bounds");
+		let len = self.data.len();
+		let mut old = mem::take(&mut self.data).into_iter();
+		let mut new = Vector::with_capacity(len - self.cols);
+		let mut removed = Vector::with_capacity(self.cols);
+		for i in 0..len {
+			let elem = old.next().expect("Grid was shorter than its dimensions?");
+			if i / self.cols == row {
+				removed.push(elem);
+			} else {
+				new.push(elem);
+			}
+		}
+		self.data = new;
+		self.rows -= 1;
+		removed
+	}
+
+	///Inserts a column at index `col`, moving every column after it to the right.
+	/// If the grid has no columns, the new column decides the amount of rows.
+	///
+	/// Panics if `col` is greater than the amount of columns or the column has the wrong length. Has O(n) complexity.
+	pub fn insert_col<I: IntoIterator<Item = T>>(&mut self, col: usize, elems: I) {
+		assert!(col <= self.cols, "Column was out of bounds");
+		let elems: Vector<T> = elems.into_iter().collect();
+		if self.cols == 0 {
+			self.rows = elems.len();
+		}
+		assert_eq!(elems.len(), self.rows, "Column has the wrong length");
+		let len = self.data.len();
+		let mut old = mem::take(&mut self.data).into_iter();
+		let mut elems = elems.into_iter();
+		let mut new = Vector::with_capacity(len + self.rows);
+		for _ in 0..self.rows {
+			for c in 0..=self.cols {
+				let elem = if c == col { elems.next() } else { old.next() };
+				new.push(elem.expect("Grid was shorter than its dimensions?"));
+			}
+		}
+		self.data = new;
+		self.cols += 1;
+	}
+
+	///Removes the column at index `col`, moving every column after it to the left.
+	///
+	/// Panics if `col` is out of bounds. Has O(n) complexity.
+	pub fn remove_col(&mut self, col: usize) -> Vector<T> {
+		assert!(col < self.cols, "Column was out of bounds");
+		let len = self.data.len();
+		let mut old = mem::take(&mut self.data).into_iter();
+		let mut new = Vector::with_capacity(len - self.rows);
+		let mut removed = Vector::with_capacity(self.rows);
+		for _ in 0..self.rows {
+			for c in 0..self.cols {
+				let elem = old.next().expect("Grid was shorter than its dimensions?");
+				if c == col {
+					removed.push(elem);
+				} else {
+					new.push(elem);
+				}
+			}
+		}
+		self.data = new;
+		self.cols -= 1;
+		removed
+	}
+
+	///Changes the dimensions of the grid. Elements keep their `(row, col)` position, elements
+	/// outside the new dimensions are dropped and new positions are filled with copies of `value`.
+	///
+	/// Panics if `rows * cols` overflows a `usize`. Has O(n) complexity.
+	pub fn resize(&mut self, rows: usize, cols: usize, value: T)
+	where
+		T: Clone,
+	{
+		let len = rows.checked_mul(cols).expect("Overflow");
+		let mut old = mem::take(&mut self.data).into_iter();
+		let mut new = Vector::with_capacity(len);
+		for r in 0..rows {
+			for c in 0..cols.max(self.cols) {
+				let elem = if r < self.rows && c < self.cols {
+					old.next()
+				} else {
+					None
+				};
+				if c < cols {
+					new.push(elem.unwrap_or_else(|| value.clone()));
+				}
+			}
+		}
+		self.data = new;
+		self.rows = rows;
+		self.cols = cols;
+	}
+}
+
+///A borrowing iterator over one column of a `Vector2D`.
+pub struct Column<'a, T> {
+	pub(crate) data: &'a [T],
+	pub(crate) stride: usize,
+	pub(crate) front: usize,
+	pub(crate) back: usize,
+}
+
+impl<'a, T> Iterator for Column<'a, T> {
+	type Item = &'a T;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.front += 1;
+		self.data.get((self.front - 1) * self.stride)
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for Column<'a, T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		self.data.get(self.back * self.stride)
+	}
+}
+
+///A mutably borrowing iterator over one column of a `Vector2D`.
+pub struct ColumnMut<'a, T> {
+	pub(crate) ptr: *mut T,
+	pub(crate) stride: usize,
+	pub(crate) front: usize,
+	pub(crate) back: usize,
+	pub(crate) _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T> Iterator for ColumnMut<'a, T> {
+	type Item = &'a mut T;
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.front += 1;
+		//Safety: Rows before `back` are within the grid and every row is only returned once,
+		// so no two references alias.
+		unsafe { self.ptr.add((self.front - 1) * self.stride).as_mut() }
+	}
+}
+
+impl<'a, T> DoubleEndedIterator for ColumnMut<'a, T> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		//Safety: Rows before `back` are within the grid and every row is only returned once,
+		// so no two references alias.
+		unsafe { self.ptr.add(self.back * self.stride).as_mut() }
+	}
+}